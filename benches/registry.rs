@@ -0,0 +1,247 @@
+//! Measures registry join and lookup throughput under many concurrent sessions.
+//!
+//! Joins go through `add_player_to_queue` over real loopback TLS connections, spread over
+//! several game types so that queues fill and promote on separate shards. Every phase runs
+//! twice: against the sharded registry, and with each registry call serialized through one
+//! lock, the way the registry behaved when all of its state sat behind a single mutex.
+//!
+//! Run with the server environment, since joins need its TLS certificates and JWT settings:
+//! `cargo bench --bench registry -- <players>`. The player count defaults to 2000; each
+//! player holds two sockets, so raise the open file limit for larger runs.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use cards::{
+    config,
+    core::{
+        registry::GameRegistry,
+        types::{GameFactory, Stream, UserId},
+    },
+    games::qafoon::Qafoon,
+    network::tls,
+};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, SignatureScheme,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+    task::JoinHandle,
+};
+use tokio_rustls::{client::TlsStream, TlsAcceptor, TlsConnector};
+use uuid::Uuid;
+
+const DEFAULT_PLAYERS: usize = 2_000;
+const GAME_TYPES: usize = 4;
+const LOOKUPS_PER_SESSION: usize = 20;
+
+/// Drives one registry, optionally funnelling every call through a single lock.
+#[derive(Clone)]
+struct Harness {
+    registry: GameRegistry,
+    global_lock: Option<Arc<Mutex<()>>>,
+}
+
+impl Harness {
+    fn new(serialized: bool) -> Self {
+        let factories: HashMap<String, GameFactory> = (0..GAME_TYPES)
+            .map(|index: usize| (game_type(index), Qafoon::boxed_new as GameFactory))
+            .collect();
+        Self {
+            registry: GameRegistry::with_factories(factories),
+            global_lock: serialized.then(|| Arc::new(Mutex::new(()))),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        if self.global_lock.is_some() {
+            "global lock"
+        } else {
+            "sharded"
+        }
+    }
+
+    async fn call<T>(&self, operation: impl Future<Output = T>) -> T {
+        match &self.global_lock {
+            Some(lock) => {
+                let _guard: tokio::sync::MutexGuard<()> = lock.lock().await;
+                operation.await
+            }
+            None => operation.await,
+        }
+    }
+}
+
+/// The benchmark talks to its own loopback listener, so the certificate is not checked.
+#[derive(Debug)]
+struct AcceptAnyCertificate;
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        rustls::crypto::ring::default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let players: usize = std::env::args()
+        .skip(1)
+        .find_map(|arg: String| arg.parse().ok())
+        .unwrap_or(DEFAULT_PLAYERS);
+    config::init_config().expect("The benchmark needs the server environment");
+    tls::init_crypto_provider();
+    let acceptor: TlsAcceptor = tls::get_tls_acceptor().expect("Failed to load TLS certificates");
+    let connector: TlsConnector = TlsConnector::from(Arc::new(
+        ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate))
+            .with_no_client_auth(),
+    ));
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind the benchmark listener");
+
+    for serialized in [true, false] {
+        let harness: Harness = Harness::new(serialized);
+        let (clients, servers) = open_connections(&listener, &acceptor, &connector, players).await;
+        let user_ids: Vec<UserId> = (0..players).map(|_| Uuid::new_v4()).collect();
+
+        let started: Instant = Instant::now();
+        let joins: Vec<JoinHandle<()>> = user_ids
+            .iter()
+            .zip(servers)
+            .enumerate()
+            .map(|(index, (user_id, connection))| {
+                let harness: Harness = harness.clone();
+                let user_id: UserId = *user_id;
+                tokio::spawn(async move {
+                    let registry: &GameRegistry = &harness.registry;
+                    let joined = harness.call(registry.add_player_to_queue(
+                        user_id,
+                        user_id.to_string(),
+                        game_type(index % GAME_TYPES),
+                        Uuid::new_v4(),
+                        connection,
+                    ));
+                    if let Err(err) = joined.await {
+                        eprintln!("Join of {user_id} failed: {err}");
+                    }
+                })
+            })
+            .collect();
+        futures::future::join_all(joins).await;
+        report("join", harness.label(), players, started.elapsed());
+
+        let started: Instant = Instant::now();
+        let lookups: Vec<JoinHandle<()>> = user_ids
+            .iter()
+            .map(|user_id: &UserId| {
+                let harness: Harness = harness.clone();
+                let user_id: UserId = *user_id;
+                tokio::spawn(async move {
+                    let registry: &GameRegistry = &harness.registry;
+                    for _ in 0..LOOKUPS_PER_SESSION {
+                        if let Some(session) =
+                            harness.call(registry.get_user_session(user_id)).await
+                        {
+                            let _ = harness
+                                .call(registry.get_active_game_sender(session.game_id))
+                                .await;
+                        }
+                    }
+                })
+            })
+            .collect();
+        futures::future::join_all(lookups).await;
+        report(
+            "lookup",
+            harness.label(),
+            players * LOOKUPS_PER_SESSION,
+            started.elapsed(),
+        );
+
+        for (game_id, _) in harness.registry.list_active_games().await {
+            let _ = harness
+                .registry
+                .shutdown_game(game_id, "Benchmark finished".to_string())
+                .await;
+        }
+        drop(clients);
+    }
+}
+
+/// Opens the player connections up front, so TLS handshakes stay out of the measurements.
+async fn open_connections(
+    listener: &TcpListener,
+    acceptor: &TlsAcceptor,
+    connector: &TlsConnector,
+    players: usize,
+) -> (Vec<TlsStream<TcpStream>>, Vec<Stream>) {
+    let address: std::net::SocketAddr = listener.local_addr().expect("Listener has no address");
+    let server_name: ServerName<'static> =
+        ServerName::try_from("localhost").expect("Invalid server name");
+    let mut clients: Vec<TlsStream<TcpStream>> = Vec::with_capacity(players);
+    let mut servers: Vec<Stream> = Vec::with_capacity(players);
+    for _ in 0..players {
+        let (client, accepted) = tokio::join!(
+            async {
+                let socket: TcpStream = TcpStream::connect(address).await?;
+                connector.connect(server_name.clone(), socket).await
+            },
+            async {
+                let (socket, _) = listener.accept().await?;
+                acceptor.accept(socket).await
+            }
+        );
+        clients.push(client.expect("Client handshake failed"));
+        servers.push(Stream::Server(accepted.expect("Server handshake failed")));
+    }
+    (clients, servers)
+}
+
+fn game_type(index: usize) -> String {
+    format!("Qafoon-{index}")
+}
+
+fn report(phase: &str, mode: &str, operations: usize, elapsed: Duration) {
+    let throughput: f64 = operations as f64 / elapsed.as_secs_f64();
+    println!("{phase:>6} ({mode:>11}): {operations} ops in {elapsed:?} ({throughput:.0} ops/s)");
+}
@@ -0,0 +1,9 @@
+-- ==================================================
+-- migrations/003_grant_existing_admin_permissions.sql
+-- ==================================================
+
+-- Admin routes now check permissions, so admins created before that keep the access they
+-- had. Managing other admins is new and has to be granted explicitly with cards-admin.
+UPDATE admins
+SET permissions = ARRAY['users:read', 'users:lock', 'users:delete', 'games:manage']
+WHERE permissions = '{}';
@@ -0,0 +1,23 @@
+-- ==================================================
+-- migrations/004_create_admin_audit_log_table.sql
+-- ==================================================
+
+CREATE TABLE admin_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    admin_id UUID NOT NULL REFERENCES admins(id),
+    admin_username VARCHAR(50) NOT NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(32) NOT NULL,
+    target_id TEXT,
+    reason TEXT,
+    details JSONB DEFAULT '{}' NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    request_path TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_admin_audit_log_created_at ON admin_audit_log (created_at DESC);
+CREATE INDEX idx_admin_audit_log_admin_id ON admin_audit_log (admin_id);
+CREATE INDEX idx_admin_audit_log_action ON admin_audit_log (action);
+CREATE INDEX idx_admin_audit_log_target ON admin_audit_log (target_type, target_id);
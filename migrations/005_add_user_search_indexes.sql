@@ -0,0 +1,8 @@
+-- ==================================================
+-- migrations/005_add_user_search_indexes.sql
+-- ==================================================
+
+-- Keyset pagination in the admin user search orders by one of these columns and the id.
+CREATE INDEX idx_users_created_at ON users (created_at, id);
+CREATE INDEX idx_users_last_login ON users ((COALESCE(last_login, TIMESTAMP 'epoch')), id);
+CREATE INDEX idx_users_games_played ON users (games_played, id);
@@ -0,0 +1,20 @@
+-- ==================================================
+-- migrations/006_create_user_bans_table.sql
+-- ==================================================
+
+CREATE TABLE user_bans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    admin_id UUID NOT NULL REFERENCES admins(id),
+    scope VARCHAR(16) NOT NULL CHECK (scope IN ('login', 'matchmaking', 'chat')),
+    reason TEXT NOT NULL,
+    starts_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP,
+    lifted_at TIMESTAMP,
+    lifted_by UUID REFERENCES admins(id),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- A ban is in effect between starts_at and expires_at unless it was lifted, so expired
+-- bans stop applying without any cleanup job.
+CREATE INDEX idx_user_bans_user_id ON user_bans (user_id) WHERE lifted_at IS NULL;
@@ -0,0 +1,27 @@
+-- ==================================================
+-- migrations/007_create_player_reports_table.sql
+-- ==================================================
+
+CREATE TABLE player_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL,
+    game_type VARCHAR(50) NOT NULL,
+    reporter_id UUID NOT NULL REFERENCES users(id),
+    reported_user_id UUID NOT NULL REFERENCES users(id),
+    category VARCHAR(16) NOT NULL CHECK (category IN ('cheating', 'abuse', 'stalling', 'other')),
+    description TEXT NOT NULL DEFAULT '',
+    context JSONB NOT NULL DEFAULT '{}'::jsonb,
+    status VARCHAR(16) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'actioned', 'dismissed')),
+    resolved_by UUID REFERENCES admins(id),
+    resolved_at TIMESTAMP,
+    resolution_note TEXT,
+    ban_id UUID REFERENCES user_bans(id),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CHECK (reporter_id <> reported_user_id)
+);
+
+-- A player can have only one open report against the same player per game.
+CREATE UNIQUE INDEX idx_player_reports_open_unique
+    ON player_reports (reporter_id, reported_user_id, game_id) WHERE status = 'open';
+CREATE INDEX idx_player_reports_status_created_at ON player_reports (status, created_at);
+CREATE INDEX idx_player_reports_reported_user_id ON player_reports (reported_user_id);
@@ -0,0 +1,20 @@
+-- ==================================================
+-- migrations/008_create_refresh_tokens_table.sql
+-- ==================================================
+
+-- Refresh tokens rotate on every use: each one is single-use and replaced by a new token in
+-- the same family. Only a SHA-256 hash of the token is stored. Revoking a family revokes
+-- every token in it, along with the access tokens they issued.
+CREATE TABLE refresh_tokens (
+    id UUID PRIMARY KEY,
+    family_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id),
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_refresh_tokens_family_id ON refresh_tokens (family_id);
+CREATE INDEX idx_refresh_tokens_user_id ON refresh_tokens (user_id) WHERE revoked_at IS NULL;
@@ -0,0 +1,179 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+
+use super::audit::{self, RequestMetadata};
+use crate::{
+    api::models::{
+        AdminPermissionsResponse, CreateAdminRequest, PermissionRequest, ResetPasswordRequest,
+    },
+    auth::Permission,
+    database::{
+        Admin, AdminInfo, AdminRepository, AuditAction, AuditLogRepository, NewAuditLogEntry,
+    },
+    prelude::*,
+};
+
+pub async fn list_admins(
+    State(admin_repo): State<AdminRepository>,
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<Vec<AdminInfo>>, StatusCode> {
+    match admin_repo.list_admins().await {
+        Ok(admins) => Ok(Json(admins.into_iter().map(AdminInfo::from).collect())),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn create_admin(
+    State(admin_repo): State<AdminRepository>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<CreateAdminRequest>,
+) -> Result<Json<AdminInfo>, StatusCode> {
+    match crate::auth::create_admin(
+        &admin_repo,
+        payload.email,
+        payload.username,
+        payload.password,
+        payload.permissions,
+    )
+    .await
+    {
+        Ok(Some(admin)) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::AdminCreate,
+                Some(admin.id.to_string()),
+            );
+            entry.details = serde_json::json!({
+                "username": admin.username,
+                "permissions": admin.permissions,
+            });
+            audit::record(&audit_repo, entry).await;
+            Ok(Json(admin.into()))
+        }
+        Ok(None) => Err(StatusCode::CONFLICT),
+        Err(Error::Validator(_)) => Err(StatusCode::BAD_REQUEST),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn delete_admin(
+    State(admin_repo): State<AdminRepository>,
+    Path(admin_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+) -> Result<StatusCode, StatusCode> {
+    if admin_id == admin_user.id {
+        return Err(StatusCode::CONFLICT);
+    }
+    match admin_repo.delete_admin(admin_id).await {
+        Ok(_) => {
+            let entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::AdminDelete,
+                Some(admin_id.to_string()),
+            );
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
+        Err(Error::UserIdNotFound(_)) => Err(StatusCode::NOT_FOUND),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn reset_password(
+    State(admin_repo): State<AdminRepository>,
+    Path(admin_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<StatusCode, StatusCode> {
+    match crate::auth::reset_admin_password(&admin_repo, admin_id, payload.password).await {
+        Ok(_) => {
+            let entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::AdminPasswordReset,
+                Some(admin_id.to_string()),
+            );
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
+        Err(Error::Validator(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(Error::UserIdNotFound(_)) => Err(StatusCode::NOT_FOUND),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn grant_permission(
+    State(admin_repo): State<AdminRepository>,
+    Path(admin_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<PermissionRequest>,
+) -> Result<Json<AdminPermissionsResponse>, StatusCode> {
+    let permission: Permission = payload
+        .permission
+        .parse()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    match admin_repo
+        .grant_permission(admin_id, permission.as_str())
+        .await
+    {
+        Ok(permissions) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::PermissionGrant,
+                Some(admin_id.to_string()),
+            );
+            entry.details = serde_json::json!({ "permission": permission.as_str() });
+            audit::record(&audit_repo, entry).await;
+            Ok(Json(AdminPermissionsResponse {
+                admin_id,
+                permissions,
+            }))
+        }
+        Err(Error::UserIdNotFound(_)) => Err(StatusCode::NOT_FOUND),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn revoke_permission(
+    State(admin_repo): State<AdminRepository>,
+    Path((admin_id, permission)): Path<(UserId, String)>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+) -> Result<Json<AdminPermissionsResponse>, StatusCode> {
+    let permission: Permission = permission.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    if admin_id == admin_user.id && permission == Permission::AdminsManage {
+        return Err(StatusCode::CONFLICT);
+    }
+    match admin_repo
+        .revoke_permission(admin_id, permission.as_str())
+        .await
+    {
+        Ok(permissions) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::PermissionRevoke,
+                Some(admin_id.to_string()),
+            );
+            entry.details = serde_json::json!({ "permission": permission.as_str() });
+            audit::record(&audit_repo, entry).await;
+            Ok(Json(AdminPermissionsResponse {
+                admin_id,
+                permissions,
+            }))
+        }
+        Err(Error::UserIdNotFound(_)) => Err(StatusCode::NOT_FOUND),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
@@ -0,0 +1,186 @@
+use axum::{
+    extract::{ConnectInfo, FromRequestParts, OriginalUri, Query, State},
+    http::{header, request::Parts, StatusCode},
+    response::Json,
+    Extension,
+};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+};
+
+use crate::{
+    api::models::{AuditLogPage, AuditLogQuery},
+    config::get_config,
+    database::{Admin, AuditAction, AuditLogFilter, AuditLogRepository, NewAuditLogEntry},
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+/// Who sent an admin request and where it went, attached to every audit log entry.
+pub struct RequestMetadata {
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    request_path: String,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestMetadata {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let forwarded_for: Vec<&str> = parts
+            .headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value: &header::HeaderValue| value.to_str().ok())
+            .collect();
+        let ip_address: Option<String> =
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| {
+                    client_ip(
+                        address.ip(),
+                        &forwarded_for.join(","),
+                        &get_config().api_server.trusted_proxies,
+                    )
+                    .to_string()
+                });
+        let user_agent: Option<String> = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|value: &header::HeaderValue| value.to_str().ok())
+            .map(str::to_string);
+        let path: &str = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map(|OriginalUri(uri)| uri.path())
+            .unwrap_or(parts.uri.path());
+        Ok(Self {
+            ip_address,
+            user_agent,
+            request_path: format!("{} {path}", parts.method),
+        })
+    }
+}
+
+/// The address of the client behind `peer`. `X-Forwarded-For` is only followed through
+/// trusted proxies, from the closest hop back, because anyone else can put any address in it.
+fn client_ip(peer: IpAddr, forwarded_for: &str, trusted_proxies: &[IpAddr]) -> IpAddr {
+    let mut client: IpAddr = peer;
+    for hop in forwarded_for.rsplit(',') {
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+        match hop.trim().parse::<IpAddr>() {
+            Ok(address) => client = address,
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+impl RequestMetadata {
+    pub fn audit(
+        &self,
+        admin: &Admin,
+        action: AuditAction,
+        target_id: Option<String>,
+    ) -> NewAuditLogEntry {
+        NewAuditLogEntry {
+            admin_id: admin.id,
+            admin_username: admin.username.clone(),
+            action,
+            target_id,
+            reason: None,
+            details: serde_json::json!({}),
+            ip_address: self.ip_address.clone(),
+            user_agent: self.user_agent.clone(),
+            request_path: self.request_path.clone(),
+        }
+    }
+}
+
+/// Records an admin mutation after it succeeded. A failed write is logged but does not
+/// undo the action it describes.
+pub async fn record(audit_repo: &AuditLogRepository, entry: NewAuditLogEntry) {
+    if let Err(err) = audit_repo.record(&entry).await {
+        eprintln!(
+            "Failed to audit {} by admin {}: {err}",
+            entry.action.as_str(),
+            entry.admin_username
+        );
+    }
+}
+
+pub async fn list_audit_log(
+    State(audit_repo): State<AuditLogRepository>,
+    Extension(_admin_user): Extension<Admin>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<AuditLogPage>, StatusCode> {
+    let page: i64 = query.page.unwrap_or(1).max(1);
+    let per_page: i64 = query
+        .per_page
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let filter: AuditLogFilter = AuditLogFilter {
+        admin_id: query.admin_id,
+        action: query.action,
+        target_type: query.target_type,
+        target_id: query.target_id,
+        from: query.from.map(|from| from.naive_utc()),
+        to: query.to.map(|to| to.naive_utc()),
+    };
+    let offset: i64 = (page - 1)
+        .checked_mul(per_page)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    match audit_repo.list(&filter, per_page, offset).await {
+        Ok((entries, total)) => Ok(Json(AuditLogPage {
+            entries,
+            page,
+            per_page,
+            total,
+        })),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_from_untrusted_peers() {
+        assert_eq!(
+            client_ip(ip("203.0.113.7"), "10.0.0.1", &[]),
+            ip("203.0.113.7")
+        );
+        assert_eq!(
+            client_ip(ip("203.0.113.7"), "10.0.0.1", &[ip("127.0.0.1")]),
+            ip("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn forwarded_for_is_followed_through_trusted_proxies() {
+        let proxies: Vec<IpAddr> = vec![ip("127.0.0.1"), ip("10.0.0.2")];
+        assert_eq!(
+            client_ip(ip("127.0.0.1"), "198.51.100.4", &proxies),
+            ip("198.51.100.4")
+        );
+        assert_eq!(
+            client_ip(ip("127.0.0.1"), "1.2.3.4, 198.51.100.4, 10.0.0.2", &proxies),
+            ip("198.51.100.4")
+        );
+        assert_eq!(
+            client_ip(ip("127.0.0.1"), "1.2.3.4, not-an-ip", &proxies),
+            ip("127.0.0.1")
+        );
+        assert_eq!(client_ip(ip("127.0.0.1"), "", &proxies), ip("127.0.0.1"));
+    }
+}
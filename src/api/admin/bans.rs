@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+
+use super::audit::{self, RequestMetadata};
+use crate::{
+    api::models::CreateBanRequest,
+    core::{get_game_registry, GameRegistry},
+    database::{
+        Admin, AuditAction, AuditLogRepository, BanScope, NewAuditLogEntry, NewUserBan, UserBan,
+        UserRepository,
+    },
+    models::UserSessionStatus,
+    prelude::*,
+};
+
+/// Validates and stores a ban, then applies it to the user's current session.
+pub(super) async fn issue_ban(
+    user_repo: &UserRepository,
+    admin_user: &Admin,
+    user_id: UserId,
+    payload: &CreateBanRequest,
+) -> Result<UserBan, StatusCode> {
+    let new_ban: NewUserBan = prepare_ban(user_repo, admin_user, user_id, payload).await?;
+    let ban: UserBan = user_repo
+        .create_ban(&new_ban)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    enforce_ban(user_repo, &ban, new_ban.scope).await;
+    Ok(ban)
+}
+
+/// Checks a ban request against an existing user and turns it into the ban to store.
+pub(super) async fn prepare_ban(
+    user_repo: &UserRepository,
+    admin_user: &Admin,
+    user_id: UserId,
+    payload: &CreateBanRequest,
+) -> Result<NewUserBan, StatusCode> {
+    if payload.reason.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let starts_at: NaiveDateTime = payload
+        .starts_at
+        .map(|starts_at| starts_at.naive_utc())
+        .unwrap_or(Utc::now().naive_utc());
+    let expires_at: Option<NaiveDateTime> = match (payload.expires_at, payload.duration_minutes) {
+        (Some(_), Some(_)) => return Err(StatusCode::BAD_REQUEST),
+        (Some(expires_at), None) => Some(expires_at.naive_utc()),
+        (None, Some(minutes)) if minutes > 0 => Some(starts_at + TimeDelta::minutes(minutes)),
+        (None, Some(_)) => return Err(StatusCode::BAD_REQUEST),
+        (None, None) => None,
+    };
+    if expires_at.is_some_and(|expires_at: NaiveDateTime| expires_at <= starts_at) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    match user_repo.get_user_by_id(user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+    Ok(NewUserBan {
+        user_id,
+        admin_id: admin_user.id,
+        scope: payload.scope,
+        reason: payload.reason.trim().to_string(),
+        starts_at: Some(starts_at),
+        expires_at,
+    })
+}
+
+/// Applies a stored ban to the user's current session: a queued user is pulled out of the
+/// queue, while a user already playing is handled by `enforce_in_game`.
+pub(super) async fn enforce_ban(user_repo: &UserRepository, ban: &UserBan, scope: BanScope) {
+    let user_id: UserId = ban.user_id;
+    if scope == BanScope::Login && ban.is_active(Utc::now().naive_utc()) {
+        if let Err(err) = user_repo.revoke_user_tokens(user_id).await {
+            eprintln!("Failed to revoke the tokens of banned user {user_id}: {err}");
+        }
+    }
+    if scope != BanScope::Chat && ban.is_active(Utc::now().naive_utc()) {
+        let registry: &GameRegistry = get_game_registry();
+        match registry.get_user_session(user_id).await {
+            Some(session) if session.status == UserSessionStatus::InGame => {
+                enforce_in_game(registry, ban, scope, session.game_id).await;
+            }
+            _ => {
+                if let Err(err) = registry.kick_from_queue(user_id, ban.notice()).await {
+                    eprintln!("Failed to remove banned user {user_id} from their queue: {err}");
+                }
+            }
+        }
+    }
+}
+
+/// A login ban removes the player from their running game like an admin kick, so a bot
+/// takes the seat and the player cannot reclaim it. A matchmaking ban only stops new games:
+/// the player is told about it but may finish, and reconnect to, the game they are in.
+async fn enforce_in_game(registry: &GameRegistry, ban: &UserBan, scope: BanScope, game_id: GameId) {
+    let user_id: UserId = ban.user_id;
+    let result: Result<()> = match scope {
+        BanScope::Login => registry.kick_player(game_id, user_id, ban.notice()).await,
+        _ => {
+            let message: GameMessage = GameMessage::Banned {
+                message: format!("{} (you can finish your current game)", ban.notice()),
+            };
+            registry.tell_player(game_id, user_id, message).await
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to apply the ban of user {user_id} to game {game_id}: {err}");
+    }
+}
+
+pub async fn create_ban(
+    State(user_repo): State<UserRepository>,
+    Path(user_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<CreateBanRequest>,
+) -> Result<Json<UserBan>, StatusCode> {
+    let ban: UserBan = issue_ban(&user_repo, &admin_user, user_id, &payload).await?;
+    let mut entry: NewAuditLogEntry =
+        metadata.audit(&admin_user, AuditAction::UserBan, Some(user_id.to_string()));
+    entry.reason = Some(ban.reason.clone());
+    entry.details = serde_json::json!({
+        "ban_id": ban.id,
+        "scope": ban.scope,
+        "starts_at": ban.starts_at,
+        "expires_at": ban.expires_at,
+    });
+    audit::record(&audit_repo, entry).await;
+    Ok(Json(ban))
+}
+
+pub async fn list_bans(
+    State(user_repo): State<UserRepository>,
+    Path(user_id): Path<UserId>,
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<Vec<UserBan>>, StatusCode> {
+    match user_repo.list_user_bans(user_id).await {
+        Ok(bans) => Ok(Json(bans)),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn lift_ban(
+    State(user_repo): State<UserRepository>,
+    Path(ban_id): Path<uuid::Uuid>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+) -> Result<Json<UserBan>, StatusCode> {
+    let ban: UserBan = match user_repo.lift_ban(ban_id, admin_user.id).await {
+        Ok(Some(ban)) => ban,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let mut entry: NewAuditLogEntry = metadata.audit(
+        &admin_user,
+        AuditAction::BanLift,
+        Some(ban.user_id.to_string()),
+    );
+    entry.details = serde_json::json!({ "ban_id": ban.id, "scope": ban.scope });
+    audit::record(&audit_repo, entry).await;
+    Ok(Json(ban))
+}
@@ -0,0 +1,216 @@
+use axum::{extract::Path, http::StatusCode, response::Json, Extension};
+use std::time::SystemTime;
+
+use super::audit::{self, RequestMetadata};
+use crate::{
+    api::models::*,
+    core::{get_game_registry, ActiveGameInfo, GameRegistry, QueueInfo},
+    database::{Admin, AuditAction, AuditLogRepository, NewAuditLogEntry},
+    models::{GameSummary, UserSession, UserSessionStatus},
+    prelude::*,
+};
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+async fn describe_game(registry: &GameRegistry, game: ActiveGameInfo) -> AdminGameInfo {
+    let summary: Option<GameSummary> = registry.get_game_summary(game.id).await.ok();
+    AdminGameInfo {
+        game_id: game.id,
+        game_type: game.game_type,
+        started_at: unix_seconds(game.started_at),
+        player_ids: game.player_ids,
+        summary,
+    }
+}
+
+pub async fn list_games(
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<Vec<AdminGameInfo>>, StatusCode> {
+    let registry: &GameRegistry = get_game_registry();
+    let games: Vec<AdminGameInfo> = futures::future::join_all(
+        registry
+            .list_active_game_infos()
+            .await
+            .into_iter()
+            .map(|game: ActiveGameInfo| describe_game(registry, game)),
+    )
+    .await;
+    Ok(Json(games))
+}
+
+pub async fn get_game(
+    Path(game_id): Path<GameId>,
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<AdminGameDetails>, StatusCode> {
+    let registry: &GameRegistry = get_game_registry();
+    let game: ActiveGameInfo = registry
+        .get_active_game_info(game_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let state: Value = match registry.inspect_game(game_id).await {
+        Ok(state) => state,
+        Err(Error::Timeout(_)) => return Err(StatusCode::GATEWAY_TIMEOUT),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    Ok(Json(AdminGameDetails {
+        game: describe_game(registry, game).await,
+        state,
+    }))
+}
+
+pub async fn end_game(
+    Path(game_id): Path<GameId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<AdminActionRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let registry: &GameRegistry = get_game_registry();
+    let game: ActiveGameInfo = registry
+        .get_active_game_info(game_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    match registry
+        .shutdown_game(game_id, payload.reason.clone())
+        .await
+    {
+        Ok(_) => {
+            let mut entry: NewAuditLogEntry =
+                metadata.audit(&admin_user, AuditAction::GameEnd, Some(game_id.to_string()));
+            entry.reason = Some(payload.reason);
+            entry.details = serde_json::json!({
+                "game_type": game.game_type,
+                "player_ids": game.player_ids,
+            });
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn kick_player(
+    Path(user_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<AdminActionRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let registry: &GameRegistry = get_game_registry();
+    let session: UserSession = registry
+        .get_user_session(user_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let result: Result<()> = match session.status {
+        UserSessionStatus::InQueue => registry.remove_player_from_queue(user_id).await,
+        UserSessionStatus::InGame => {
+            let kicked: Result<()> = registry
+                .kick_player(session.game_id, user_id, payload.reason.clone())
+                .await;
+            let _ = registry.force_remove_user_session(user_id).await;
+            kicked
+        }
+    };
+    match result {
+        Ok(_) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::PlayerKick,
+                Some(user_id.to_string()),
+            );
+            entry.reason = Some(payload.reason);
+            entry.details = serde_json::json!({
+                "game_id": session.game_id,
+                "game_type": session.game_type,
+                "status": session.status.as_str(),
+            });
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn list_queues(
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<Vec<AdminQueueInfo>>, StatusCode> {
+    let queues: Vec<AdminQueueInfo> = get_game_registry()
+        .list_queues()
+        .await
+        .into_iter()
+        .map(|queue: QueueInfo| AdminQueueInfo {
+            game_type: queue.game_type,
+            game_id: queue.game_id,
+            created_at: unix_seconds(queue.created_at),
+            player_ids: queue.player_ids,
+            status: queue.status,
+        })
+        .collect();
+    Ok(Json(queues))
+}
+
+pub async fn list_sessions(
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<Vec<AdminSessionInfo>>, StatusCode> {
+    let sessions: Vec<AdminSessionInfo> = get_game_registry()
+        .list_user_sessions()
+        .await
+        .into_iter()
+        .map(|session: UserSession| AdminSessionInfo {
+            user_id: session.user_id,
+            username: session.username,
+            game_id: session.game_id,
+            game_type: session.game_type,
+            status: session.status.as_str().to_string(),
+            joined_at: unix_seconds(session.joined_at),
+        })
+        .collect();
+    Ok(Json(sessions))
+}
+
+pub async fn clear_session(
+    Path(user_id): Path<UserId>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+) -> Result<StatusCode, StatusCode> {
+    let registry: &GameRegistry = get_game_registry();
+    let session: UserSession = registry
+        .get_user_session(user_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    match registry.force_remove_user_session(user_id).await {
+        Ok(_) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::SessionClear,
+                Some(user_id.to_string()),
+            );
+            entry.details = serde_json::json!({
+                "game_id": session.game_id,
+                "game_type": session.game_type,
+                "status": session.status.as_str(),
+            });
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn clear_stale_sessions(
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+) -> Result<Json<ClearSessionsResponse>, StatusCode> {
+    let cleared: usize = get_game_registry().clear_stale_sessions().await;
+    let mut entry: NewAuditLogEntry =
+        metadata.audit(&admin_user, AuditAction::StaleSessionsClear, None);
+    entry.details = serde_json::json!({ "cleared": cleared });
+    audit::record(&audit_repo, entry).await;
+    Ok(Json(ClearSessionsResponse { cleared }))
+}
@@ -1,20 +1,85 @@
+pub mod admins;
+pub mod audit;
+pub mod bans;
+pub mod games;
+pub mod reports;
 pub mod users;
 
 use axum::{
+    middleware::from_fn_with_state,
     routing::{delete, get, post},
-    Router,
+    Extension, Router,
 };
 
+use crate::{
+    auth::Permission,
+    database::{AdminRepository, AuditLogRepository, ReportRepository, UserRepository},
+};
+
+fn require(permission: Permission, router: Router<UserRepository>) -> Router<UserRepository> {
+    router.route_layer(from_fn_with_state(
+        permission,
+        super::middleware::require_permission,
+    ))
+}
+
+/// Groups the admin routes by the permission they require. Admins holding `admins:manage`
+/// act as super-admins: they manage other admin accounts and their permissions.
 pub fn create_admin_router(
-    admin_repo: crate::database::AdminRepository,
-) -> Router<crate::database::UserRepository> {
-    Router::new()
+    admin_repo: AdminRepository,
+    audit_repo: AuditLogRepository,
+    report_repo: ReportRepository,
+) -> Router<UserRepository> {
+    let users_read: Router<UserRepository> = Router::new()
+        .route("/users", get(users::list_users))
         .route("/users/{id}", get(users::get_user))
+        .route("/users/{id}/bans", get(bans::list_bans))
+        .route("/reports", get(reports::list_reports))
+        .route("/reports/{id}", get(reports::get_report));
+    let users_lock: Router<UserRepository> = Router::new()
         .route("/users/{id}/lock", post(users::lock_user))
         .route("/users/{id}/unlock", post(users::unlock_user))
-        .route("/users/{id}", delete(users::delete_user))
-        .layer(axum::middleware::from_fn_with_state(
+        .route("/users/{id}/bans", post(bans::create_ban))
+        .route("/bans/{id}", delete(bans::lift_ban))
+        .route("/reports/{id}/action", post(reports::action_report))
+        .route("/reports/{id}/dismiss", post(reports::dismiss_report))
+        .route("/reports/{id}/ban", post(reports::ban_from_report));
+    let users_delete: Router<UserRepository> =
+        Router::new().route("/users/{id}", delete(users::delete_user));
+    let games_manage: Router<UserRepository> = Router::new()
+        .route("/games", get(games::list_games))
+        .route("/games/{id}", get(games::get_game))
+        .route("/games/{id}/end", post(games::end_game))
+        .route("/players/{id}/kick", post(games::kick_player))
+        .route("/queues", get(games::list_queues))
+        .route("/sessions", get(games::list_sessions))
+        .route("/sessions/{id}", delete(games::clear_session))
+        .route("/sessions/clear-stale", post(games::clear_stale_sessions));
+    let admins_manage: Router<UserRepository> = Router::new()
+        .route("/admins", get(admins::list_admins))
+        .route("/admins", post(admins::create_admin))
+        .route("/admins/{id}", delete(admins::delete_admin))
+        .route("/admins/{id}/password", post(admins::reset_password))
+        .route("/admins/{id}/permissions", post(admins::grant_permission))
+        .route(
+            "/admins/{id}/permissions/{permission}",
+            delete(admins::revoke_permission),
+        )
+        .with_state(admin_repo.clone());
+    let audit_log: Router<UserRepository> = Router::new()
+        .route("/audit-log", get(audit::list_audit_log))
+        .with_state(audit_repo.clone());
+    Router::new()
+        .merge(require(Permission::UsersRead, users_read))
+        .merge(require(Permission::UsersLock, users_lock))
+        .merge(require(Permission::UsersDelete, users_delete))
+        .merge(require(Permission::GamesManage, games_manage))
+        .merge(require(Permission::AdminsManage, admins_manage))
+        .merge(require(Permission::AdminsManage, audit_log))
+        .layer(from_fn_with_state(
             admin_repo,
             super::middleware::admin_auth_middleware,
         ))
+        .layer(Extension(audit_repo))
+        .layer(Extension(report_repo))
 }
@@ -0,0 +1,207 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+
+use super::{
+    audit::{self, RequestMetadata},
+    bans::{enforce_ban, prepare_ban},
+};
+use crate::{
+    api::models::{
+        AdminActionRequest, CreateBanRequest, ReportBanResponse, ReportPage, ReportQuery,
+    },
+    database::{
+        Admin, AuditAction, AuditLogRepository, NewAuditLogEntry, NewUserBan, PlayerReport,
+        ReportFilter, ReportRepository, ReportStatus, UserBan, UserRepository,
+    },
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+async fn get_open_report(
+    report_repo: &ReportRepository,
+    report_id: uuid::Uuid,
+) -> Result<PlayerReport, StatusCode> {
+    match report_repo.get_report(report_id).await {
+        Ok(Some(report)) if report.status == ReportStatus::Open.as_str() => Ok(report),
+        Ok(Some(_)) => Err(StatusCode::CONFLICT),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Closes an open report. A report resolved concurrently by another admin yields a
+/// conflict.
+async fn resolve(
+    report_repo: &ReportRepository,
+    admin_user: &Admin,
+    report_id: uuid::Uuid,
+    status: ReportStatus,
+    note: &str,
+    ban_id: Option<uuid::Uuid>,
+) -> Result<PlayerReport, StatusCode> {
+    match report_repo
+        .resolve_report(report_id, admin_user.id, status, note, ban_id)
+        .await
+    {
+        Ok(Some(report)) => Ok(report),
+        Ok(None) => Err(StatusCode::CONFLICT),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn audit_resolution(
+    audit_repo: &AuditLogRepository,
+    admin_user: &Admin,
+    metadata: &RequestMetadata,
+    report: &PlayerReport,
+) {
+    let action: AuditAction = match report.status.parse::<ReportStatus>() {
+        Ok(ReportStatus::Dismissed) => AuditAction::ReportDismiss,
+        _ => AuditAction::ReportAction,
+    };
+    let mut entry: NewAuditLogEntry =
+        metadata.audit(admin_user, action, Some(report.id.to_string()));
+    entry.reason = report.resolution_note.clone();
+    entry.details = serde_json::json!({
+        "reported_user_id": report.reported_user_id,
+        "game_id": report.game_id,
+        "category": report.category,
+        "ban_id": report.ban_id,
+    });
+    audit::record(audit_repo, entry).await;
+}
+
+pub async fn list_reports(
+    Extension(report_repo): Extension<ReportRepository>,
+    Extension(_admin_user): Extension<Admin>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Json<ReportPage>, StatusCode> {
+    let page: i64 = query.page.unwrap_or(1).max(1);
+    let per_page: i64 = query
+        .per_page
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let filter: ReportFilter = ReportFilter {
+        status: query.status,
+        category: query.category,
+        reporter_id: query.reporter_id,
+        reported_user_id: query.reported_user_id,
+        game_id: query.game_id,
+    };
+    let offset: i64 = (page - 1)
+        .checked_mul(per_page)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    match report_repo.list_reports(&filter, per_page, offset).await {
+        Ok((reports, total)) => Ok(Json(ReportPage {
+            reports,
+            page,
+            per_page,
+            total,
+        })),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn get_report(
+    Extension(report_repo): Extension<ReportRepository>,
+    Path(report_id): Path<uuid::Uuid>,
+    Extension(_admin_user): Extension<Admin>,
+) -> Result<Json<PlayerReport>, StatusCode> {
+    match report_repo.get_report(report_id).await {
+        Ok(Some(report)) => Ok(Json(report)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Marks a report as handled without banning, e.g. after warning the player.
+pub async fn action_report(
+    Extension(report_repo): Extension<ReportRepository>,
+    Path(report_id): Path<uuid::Uuid>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<AdminActionRequest>,
+) -> Result<Json<PlayerReport>, StatusCode> {
+    get_open_report(&report_repo, report_id).await?;
+    let report: PlayerReport = resolve(
+        &report_repo,
+        &admin_user,
+        report_id,
+        ReportStatus::Actioned,
+        &payload.reason,
+        None,
+    )
+    .await?;
+    audit_resolution(&audit_repo, &admin_user, &metadata, &report).await;
+    Ok(Json(report))
+}
+
+pub async fn dismiss_report(
+    Extension(report_repo): Extension<ReportRepository>,
+    Path(report_id): Path<uuid::Uuid>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<AdminActionRequest>,
+) -> Result<Json<PlayerReport>, StatusCode> {
+    get_open_report(&report_repo, report_id).await?;
+    let report: PlayerReport = resolve(
+        &report_repo,
+        &admin_user,
+        report_id,
+        ReportStatus::Dismissed,
+        &payload.reason,
+        None,
+    )
+    .await?;
+    audit_resolution(&audit_repo, &admin_user, &metadata, &report).await;
+    Ok(Json(report))
+}
+
+/// Bans the reported player and closes the report as actioned, linking it to the ban.
+pub async fn ban_from_report(
+    State(user_repo): State<UserRepository>,
+    Extension(report_repo): Extension<ReportRepository>,
+    Path(report_id): Path<uuid::Uuid>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    Json(payload): Json<CreateBanRequest>,
+) -> Result<Json<ReportBanResponse>, StatusCode> {
+    let report: PlayerReport = get_open_report(&report_repo, report_id).await?;
+    let new_ban: NewUserBan =
+        prepare_ban(&user_repo, &admin_user, report.reported_user_id, &payload).await?;
+    // The ban is only kept when the report is still open as it gets closed, so that a report
+    // resolved concurrently by another admin leaves no ban behind.
+    let (report, ban): (PlayerReport, UserBan) = match report_repo
+        .resolve_report_with_ban(report_id, &new_ban)
+        .await
+    {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => return Err(StatusCode::CONFLICT),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    enforce_ban(&user_repo, &ban, new_ban.scope).await;
+    let mut entry: NewAuditLogEntry = metadata.audit(
+        &admin_user,
+        AuditAction::UserBan,
+        Some(report.reported_user_id.to_string()),
+    );
+    entry.reason = Some(ban.reason.clone());
+    entry.details = serde_json::json!({
+        "ban_id": ban.id,
+        "scope": ban.scope,
+        "starts_at": ban.starts_at,
+        "expires_at": ban.expires_at,
+        "report_id": report.id,
+    });
+    audit::record(&audit_repo, entry).await;
+    audit_resolution(&audit_repo, &admin_user, &metadata, &report).await;
+    Ok(Json(ReportBanResponse { report, ban }))
+}
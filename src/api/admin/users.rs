@@ -1,34 +1,101 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
     Extension,
 };
 
+use super::audit::{self, RequestMetadata};
 use crate::{
-    database::{Admin, User, UserRepository},
+    api::models::{AdminActionRequest, UserSearchPage, UserSearchQuery},
+    database::{
+        Admin, AdminUserView, AuditAction, AuditLogRepository, NewAuditLogEntry, User,
+        UserRepository, UserSearchFilter,
+    },
     prelude::*,
 };
 
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+pub async fn list_users(
+    State(user_repo): State<UserRepository>,
+    Extension(_admin_user): Extension<Admin>,
+    Query(query): Query<UserSearchQuery>,
+) -> Result<Json<UserSearchPage>, StatusCode> {
+    let limit: i64 = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let filter: UserSearchFilter = UserSearchFilter {
+        username: query.username,
+        email: query.email,
+        is_locked: query.locked,
+        is_active: query.active,
+        email_verified: query.verified,
+        created_after: query.created_after.map(|time| time.naive_utc()),
+        created_before: query.created_before.map(|time| time.naive_utc()),
+        last_login_after: query.last_login_after.map(|time| time.naive_utc()),
+        last_login_before: query.last_login_before.map(|time| time.naive_utc()),
+        min_games_played: query.min_games_played,
+        max_games_played: query.max_games_played,
+    };
+    let mut users: Vec<User> = match user_repo
+        .search_users(&filter, query.sort, query.order, query.cursor, limit + 1)
+        .await
+    {
+        Ok(users) => users,
+        _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let has_more: bool = users.len() as i64 > limit;
+    users.truncate(limit as usize);
+    let next_cursor: Option<UserId> = users.last().filter(|_| has_more).map(|user: &User| user.id);
+    Ok(Json(UserSearchPage {
+        users: users.into_iter().map(AdminUserView::from).collect(),
+        next_cursor,
+    }))
+}
+
 pub async fn get_user(
     State(user_repo): State<UserRepository>,
     Path(user_id): Path<UserId>,
     Extension(_admin_user): Extension<Admin>,
-) -> Result<Json<User>, StatusCode> {
+) -> Result<Json<AdminUserView>, StatusCode> {
     match user_repo.get_user_by_id(user_id).await {
-        Ok(Some(user)) => Ok(Json(user)),
+        Ok(Some(user)) => Ok(Json(user.into())),
         Ok(None) => Err(StatusCode::NOT_FOUND),
         _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+/// Signs the user out everywhere by revoking all their refresh token families. Their
+/// access tokens stop working on the next request.
+async fn revoke_sessions(user_repo: &UserRepository, user_id: UserId) {
+    if let Err(err) = user_repo.revoke_user_tokens(user_id).await {
+        eprintln!("Failed to revoke the tokens of user {user_id}: {err}");
+    }
+}
+
 pub async fn lock_user(
     State(user_repo): State<UserRepository>,
     Path(user_id): Path<UserId>,
-    Extension(_admin_user): Extension<Admin>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    payload: Option<Json<AdminActionRequest>>,
 ) -> Result<StatusCode, StatusCode> {
     match user_repo.lock_user(user_id).await {
-        Ok(_) => Ok(StatusCode::OK),
+        Ok(_) => {
+            revoke_sessions(&user_repo, user_id).await;
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::UserLock,
+                Some(user_id.to_string()),
+            );
+            entry.reason = payload.map(|Json(payload)| payload.reason);
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
         _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -36,10 +103,22 @@ pub async fn lock_user(
 pub async fn unlock_user(
     State(user_repo): State<UserRepository>,
     Path(user_id): Path<UserId>,
-    Extension(_admin_user): Extension<Admin>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    payload: Option<Json<AdminActionRequest>>,
 ) -> Result<StatusCode, StatusCode> {
     match user_repo.unlock_user(user_id).await {
-        Ok(_) => Ok(StatusCode::OK),
+        Ok(_) => {
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::UserUnlock,
+                Some(user_id.to_string()),
+            );
+            entry.reason = payload.map(|Json(payload)| payload.reason);
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
         _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -47,10 +126,23 @@ pub async fn unlock_user(
 pub async fn delete_user(
     State(user_repo): State<UserRepository>,
     Path(user_id): Path<UserId>,
-    Extension(_admin_user): Extension<Admin>,
+    Extension(admin_user): Extension<Admin>,
+    Extension(audit_repo): Extension<AuditLogRepository>,
+    metadata: RequestMetadata,
+    payload: Option<Json<AdminActionRequest>>,
 ) -> Result<StatusCode, StatusCode> {
     match user_repo.delete_user(user_id).await {
-        Ok(_) => Ok(StatusCode::OK),
+        Ok(_) => {
+            revoke_sessions(&user_repo, user_id).await;
+            let mut entry: NewAuditLogEntry = metadata.audit(
+                &admin_user,
+                AuditAction::UserDelete,
+                Some(user_id.to_string()),
+            );
+            entry.reason = payload.map(|Json(payload)| payload.reason);
+            audit::record(&audit_repo, entry).await;
+            Ok(StatusCode::OK)
+        }
         _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
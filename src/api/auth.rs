@@ -1,14 +1,26 @@
 use axum::{extract::State, http::StatusCode, response::Json};
 
 use super::models::{
-    AdminAuthResponse, AuthResponse, ErrorResponse, LoginRequest, RegisterRequest,
+    AdminAuthResponse, AuthResponse, ErrorResponse, LoginRequest, LogoutResponse,
+    RefreshTokenRequest, RegisterRequest,
 };
 use crate::{
-    auth::{generate_token, TokenPair},
+    auth::{generate_token, issue_user_tokens, TokenPair, UserTokens},
     database::{Admin, User, UserRepository},
     prelude::*,
 };
 
+fn user_auth_response(user: User, tokens: UserTokens) -> AuthResponse {
+    AuthResponse {
+        success: true,
+        access_token: Some(tokens.access.access_token),
+        expires_in: Some(tokens.access.expires_in),
+        refresh_token: Some(tokens.refresh_token),
+        refresh_expires_in: Some(tokens.refresh_expires_in),
+        user: Some(user.into()),
+    }
+}
+
 pub async fn login(
     State(user_repo): State<UserRepository>,
     Json(payload): Json<LoginRequest>,
@@ -16,6 +28,15 @@ pub async fn login(
     let auth_result: Option<User> =
         match crate::auth::login_user(&user_repo, payload.username, payload.password).await {
             Ok(result) => result,
+            Err(Error::Banned(message)) => {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(ErrorResponse {
+                        success: false,
+                        message,
+                    }),
+                ))
+            }
             Err(e) => {
                 return Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -33,11 +54,13 @@ pub async fn login(
                 success: false,
                 access_token: None,
                 expires_in: None,
+                refresh_token: None,
+                refresh_expires_in: None,
                 user: None,
             }));
         }
     };
-    let tokens: TokenPair = match generate_token(user.id, user.username.clone(), false) {
+    let tokens: UserTokens = match issue_user_tokens(&user_repo, &user, None).await {
         Ok(tokens) => tokens,
         Err(_) => {
             return Err((
@@ -49,12 +72,7 @@ pub async fn login(
             ))
         }
     };
-    Ok(Json(AuthResponse {
-        success: true,
-        access_token: Some(tokens.access_token),
-        expires_in: Some(tokens.expires_in),
-        user: Some(user.into()),
-    }))
+    Ok(Json(user_auth_response(user, tokens)))
 }
 
 pub async fn admin_login(
@@ -93,8 +111,7 @@ pub async fn admin_login(
             admin: None,
         }));
     }
-    let tokens: TokenPair = match generate_token(admin.id, admin.username.clone(), true)
-    {
+    let tokens: TokenPair = match generate_token(admin.id, admin.username.clone(), true) {
         Ok(tokens) => tokens,
         Err(_) => {
             return Err((
@@ -144,12 +161,13 @@ pub async fn register(
                 success: false,
                 access_token: None,
                 expires_in: None,
+                refresh_token: None,
+                refresh_expires_in: None,
                 user: None,
             }));
         }
     };
-    let tokens: TokenPair = match generate_token(user.id, user.username.clone(), false)
-    {
+    let tokens: UserTokens = match issue_user_tokens(&user_repo, &user, None).await {
         Ok(tokens) => tokens,
         Err(_) => {
             return Err((
@@ -161,10 +179,61 @@ pub async fn register(
             ))
         }
     };
-    Ok(Json(AuthResponse {
-        success: true,
-        access_token: Some(tokens.access_token),
-        expires_in: Some(tokens.expires_in),
-        user: Some(user.into()),
-    }))
+    Ok(Json(user_auth_response(user, tokens)))
+}
+
+pub async fn refresh(
+    State(user_repo): State<UserRepository>,
+    Json(payload): Json<RefreshTokenRequest>,
+) -> Result<Json<AuthResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match crate::auth::refresh_user_tokens(&user_repo, &payload.refresh_token).await {
+        Ok(Some((user, tokens))) => Ok(Json(user_auth_response(user, tokens))),
+        Ok(None) => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse {
+                success: false,
+                message: "Invalid or expired refresh token".to_string(),
+            }),
+        )),
+        Err(Error::Banned(message)) => Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                success: false,
+                message,
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                success: false,
+                message: e.to_string(),
+            }),
+        )),
+    }
+}
+
+pub async fn logout(
+    State(user_repo): State<UserRepository>,
+    Json(payload): Json<RefreshTokenRequest>,
+) -> Result<Json<LogoutResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match crate::auth::logout_user(&user_repo, &payload.refresh_token).await {
+        Ok(true) => Ok(Json(LogoutResponse {
+            success: true,
+            message: "Logged out".to_string(),
+        })),
+        Ok(false) => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse {
+                success: false,
+                message: "Invalid refresh token".to_string(),
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                success: false,
+                message: e.to_string(),
+            }),
+        )),
+    }
 }
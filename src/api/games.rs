@@ -3,10 +3,13 @@ use std::time::SystemTime;
 
 use super::{get_token, models::*};
 use crate::{
-    auth::{generate_game_session_token, validate_token, Claims},
+    auth::{
+        authorize_user, generate_game_session_token, generate_reconnection_token, validate_token,
+        Claims,
+    },
     core::{get_game_registry, GameRegistry},
-    database::UserRepository,
-    models::{UserSession, UserSessionStatus},
+    database::{BanScope, UserRepository},
+    models::{QueueStatus, UserSession, UserSessionStatus},
     prelude::*,
 };
 
@@ -38,7 +41,7 @@ pub async fn get_available_games(
             ));
         }
     };
-    match user_repo.get_user_by_id(claims.sub).await {
+    match authorize_user(&user_repo, &claims).await {
         Ok(Some(_)) => {
             let available_games: Vec<String> = get_game_registry().get_available_games();
             Ok(Json(AvailableGamesResponse {
@@ -85,8 +88,8 @@ pub async fn join_game_queue(
             ));
         }
     };
-    let user: crate::database::User = match user_repo.get_user_by_id(claims.sub).await {
-        Ok(Some(user)) => user,
+    let (user, jti) = match (authorize_user(&user_repo, &claims).await, claims.jti) {
+        (Ok(Some(user)), Some(jti)) => (user, jti),
         _ => {
             return Err((
                 StatusCode::UNAUTHORIZED,
@@ -97,7 +100,40 @@ pub async fn join_game_queue(
             ));
         }
     };
+    match user_repo
+        .get_active_ban(user.id, BanScope::Matchmaking)
+        .await
+    {
+        Ok(Some(ban)) => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse {
+                    success: false,
+                    message: ban.notice(),
+                }),
+            ));
+        }
+        Ok(None) => {}
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "Failed to check account restrictions".to_string(),
+                }),
+            ));
+        }
+    }
     let registry: &GameRegistry = get_game_registry();
+    if registry.is_draining() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                success: false,
+                message: "The server is shutting down for maintenance".to_string(),
+            }),
+        ));
+    }
     if let Some(existing_session) = registry.get_user_session(user.id).await {
         return Err((
             StatusCode::CONFLICT,
@@ -129,7 +165,8 @@ pub async fn join_game_queue(
         ));
     }
     let game_token: crate::auth::TokenPair =
-        match generate_game_session_token(user.id, user.username, payload.game_choice.clone()) {
+        match generate_game_session_token(user.id, user.username, payload.game_choice.clone(), jti)
+        {
             Ok(token) => token,
             Err(_) => {
                 return Err((
@@ -145,6 +182,7 @@ pub async fn join_game_queue(
         success: true,
         game_token: Some(game_token.access_token),
         game_choice: payload.game_choice,
+        game_server_address: get_config().game_server.public_address.clone(),
         message: "Ready to connect to game server".to_string(),
     }))
 }
@@ -177,10 +215,16 @@ pub async fn get_session_status(
             ));
         }
     };
-    match user_repo.get_user_by_id(claims.sub).await {
+    match authorize_user(&user_repo, &claims).await {
         Ok(Some(_)) => {
             let registry: &GameRegistry = get_game_registry();
             let session: Option<UserSession> = registry.get_user_session(claims.sub).await;
+            let queue: Option<QueueStatus> = match &session {
+                Some(s) if s.status == UserSessionStatus::InQueue => {
+                    registry.get_queue_status(&s.game_type).await
+                }
+                _ => None,
+            };
             let session_info: Option<SessionInfo> = session.map(|s: UserSession| SessionInfo {
                 game_type: s.game_type,
                 status: s.status.as_str().to_string(),
@@ -190,6 +234,7 @@ pub async fn get_session_status(
                     .unwrap_or_default()
                     .as_secs(),
                 game_id: s.game_id,
+                queue,
             });
             Ok(Json(UserSessionStatusResponse {
                 success: true,
@@ -207,6 +252,89 @@ pub async fn get_session_status(
     }
 }
 
+pub async fn reissue_reconnection_token(
+    headers: axum::http::HeaderMap,
+    State(user_repo): State<UserRepository>,
+) -> Result<Json<ReconnectionTokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let token: &str = match get_token(&headers) {
+        Some(token) => token,
+        None => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "Missing or invalid authorization header".to_string(),
+                }),
+            ));
+        }
+    };
+    let claims: Claims = match validate_token(token) {
+        Ok(claims) => claims,
+        Err(_) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "Invalid token".to_string(),
+                }),
+            ));
+        }
+    };
+    let jti: uuid::Uuid = match (authorize_user(&user_repo, &claims).await, claims.jti) {
+        (Ok(Some(_)), Some(jti)) => jti,
+        _ => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "User not found".to_string(),
+                }),
+            ));
+        }
+    };
+    let session: UserSession = match get_game_registry().get_user_session(claims.sub).await {
+        Some(session) if session.status == UserSessionStatus::InGame => session,
+        Some(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "Your game has not started yet".to_string(),
+                }),
+            ));
+        }
+        None => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    success: false,
+                    message: "No active game session found".to_string(),
+                }),
+            ));
+        }
+    };
+    let reconnection_token: crate::auth::TokenPair =
+        match generate_reconnection_token(session.user_id, session.game_id, jti) {
+            Ok(token) => token,
+            Err(_) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        success: false,
+                        message: "Failed to generate reconnection token".to_string(),
+                    }),
+                ));
+            }
+        };
+    Ok(Json(ReconnectionTokenResponse {
+        success: true,
+        reconnection_token: reconnection_token.access_token,
+        expires_in: reconnection_token.expires_in,
+        game_id: session.game_id,
+        game_server_address: get_config().game_server.public_address.clone(),
+    }))
+}
+
 pub async fn leave_game_session(
     headers: axum::http::HeaderMap,
     State(user_repo): State<UserRepository>,
@@ -235,13 +363,13 @@ pub async fn leave_game_session(
             ));
         }
     };
-    match user_repo.get_user_by_id(claims.sub).await {
+    match authorize_user(&user_repo, &claims).await {
         Ok(Some(_)) => {
             let registry: &GameRegistry = get_game_registry();
             if let Some(session) = registry.get_user_session(claims.sub).await {
                 match session.status {
                     UserSessionStatus::InQueue => {
-                        if let Err(e) = registry.force_remove_user_session(claims.sub).await {
+                        if let Err(e) = registry.remove_player_from_queue(claims.sub).await {
                             return Err((
                                 StatusCode::INTERNAL_SERVER_ERROR,
                                 Json(ErrorResponse {
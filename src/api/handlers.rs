@@ -1,14 +1,15 @@
 use axum::{
     response::Json,
     routing::{delete, get, post},
-    Router,
+    Extension, Router,
 };
 use std::time::SystemTime;
-use tokio::{net::TcpListener, task::JoinHandle};
+use tokio::{net::TcpListener, sync::oneshot, task::JoinHandle};
 
-use super::{admin, auth, games, models::HealthResponse};
+use super::{admin, auth, games, models::HealthResponse, reports};
 use crate::{
-    database::{AdminRepository, UserRepository},
+    core::{get_game_registry, GameRegistry},
+    database::{AdminRepository, AuditLogRepository, ReportRepository, UserRepository},
     prelude::*,
 };
 
@@ -25,17 +26,22 @@ async fn health() -> Json<HealthResponse> {
         .duration_since(start_time)
         .unwrap_or_default()
         .as_secs();
+    let registry: &GameRegistry = get_game_registry();
     Json(HealthResponse {
         status: "healthy".to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime: format!("{uptime}s"),
+        active_games: registry.get_active_games_count().await,
+        crashed_games: registry.get_crashed_games_count(),
     })
 }
 
 fn create_router(pool: PgPool) -> Router {
     let user_repo: UserRepository = UserRepository::new(pool.clone());
     let admin_repo: AdminRepository = AdminRepository::new(pool.clone());
+    let audit_repo: AuditLogRepository = AuditLogRepository::new(pool.clone());
+    let report_repo: ReportRepository = ReportRepository::new(pool.clone());
     let admin_auth_routes: Router<UserRepository> = Router::new()
         .route("/auth/admin/login", post(auth::admin_login))
         .with_state(admin_repo.clone());
@@ -43,16 +49,32 @@ fn create_router(pool: PgPool) -> Router {
         .route("/health", get(health))
         .route("/auth/login", post(auth::login))
         .route("/auth/register", post(auth::register))
+        .route("/auth/refresh", post(auth::refresh))
+        .route("/auth/logout", post(auth::logout))
         .route("/games/available", get(games::get_available_games))
         .route("/games/join", post(games::join_game_queue))
         .route("/games/session/status", get(games::get_session_status))
         .route("/games/session/leave", delete(games::leave_game_session))
+        .route(
+            "/games/session/reconnect",
+            post(games::reissue_reconnection_token),
+        )
+        .route(
+            "/games/reports",
+            post(reports::file_report).layer(Extension(report_repo.clone())),
+        )
         .merge(admin_auth_routes)
-        .nest("/admin", admin::create_admin_router(admin_repo))
+        .nest(
+            "/admin",
+            admin::create_admin_router(admin_repo, audit_repo, report_repo),
+        )
         .with_state(user_repo)
 }
 
-pub async fn init_api_server(pool: PgPool) -> Result<JoinHandle<()>> {
+pub async fn init_api_server(
+    pool: PgPool,
+    shutdown: oneshot::Receiver<()>,
+) -> Result<JoinHandle<()>> {
     let config: &Config = get_config();
     let address: &str = &format!("{}:{}", config.api_server.host, config.api_server.port);
     let api_listener: TcpListener = TcpListener::bind(address)
@@ -60,9 +82,16 @@ pub async fn init_api_server(pool: PgPool) -> Result<JoinHandle<()>> {
         .map_err(|err: std::io::Error| Error::bind_address(address, err))?;
     let app: Router = create_router(pool);
     let api_server: JoinHandle<()> = tokio::spawn(async move {
-        axum::serve(api_listener, app)
-            .await
-            .expect("API server failed");
+        axum::serve(
+            api_listener,
+            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .with_graceful_shutdown(async {
+            let _ = shutdown.await;
+        })
+        .await
+        .expect("API server failed");
+        println!("API server stopped");
     });
     println!("API server started successfully");
     Ok(api_server)
@@ -29,3 +29,20 @@ pub async fn admin_auth_middleware(
     request.extensions_mut().insert(admin);
     Ok(next.run(request).await)
 }
+
+/// Checks the permissions of the admin loaded by `admin_auth_middleware`, so it must run
+/// inside it. Granted and revoked permissions apply from the next request.
+pub async fn require_permission(
+    State(permission): State<crate::auth::Permission>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Result<axum::response::Response, StatusCode> {
+    let admin: &crate::database::Admin = request
+        .extensions()
+        .get::<crate::database::Admin>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    if !admin.has_permission(permission) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(request).await)
+}
@@ -4,5 +4,6 @@ mod games;
 mod handlers;
 mod middleware;
 mod models;
+mod reports;
 
 pub use handlers::{get_token, init_api_server};
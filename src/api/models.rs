@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    database::{AdminInfo, UserInfo},
-    prelude::GameId,
+    database::{
+        AdminInfo, AdminUserView, AuditLogEntry, BanScope, PlayerReport, ReportCategory,
+        ReportStatus, SortOrder, UserBan, UserInfo, UserSortField,
+    },
+    models::{GameSummary, QueueStatus},
+    prelude::{GameId, PlayerId, UserId, Value},
 };
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -11,6 +15,8 @@ pub struct HealthResponse {
     pub timestamp: String,
     pub version: String,
     pub uptime: String,
+    pub active_games: usize,
+    pub crashed_games: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,6 +31,8 @@ pub struct AuthResponse {
     pub user: Option<UserInfo>,
     pub access_token: Option<String>,
     pub expires_in: Option<i64>,
+    pub refresh_token: Option<String>,
+    pub refresh_expires_in: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -41,6 +49,17 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogoutResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RegisterRequest {
     pub email: String,
@@ -64,6 +83,7 @@ pub struct JoinGameResponse {
     pub success: bool,
     pub game_token: Option<String>,
     pub game_choice: String,
+    pub game_server_address: String,
     pub message: String,
 }
 
@@ -73,6 +93,7 @@ pub struct SessionInfo {
     pub status: String,
     pub joined_at: u64,
     pub game_id: GameId,
+    pub queue: Option<QueueStatus>,
 }
 
 #[derive(Debug, Serialize)]
@@ -82,8 +103,183 @@ pub struct UserSessionStatusResponse {
     pub session: Option<SessionInfo>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ReconnectionTokenResponse {
+    pub success: bool,
+    pub reconnection_token: String,
+    pub expires_in: i64,
+    pub game_id: GameId,
+    pub game_server_address: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct LeaveGameSessionResponse {
     pub success: bool,
     pub message: String,
 }
+
+#[derive(Debug, Serialize)]
+pub struct AdminGameInfo {
+    pub game_id: GameId,
+    pub game_type: String,
+    pub started_at: u64,
+    pub player_ids: Vec<PlayerId>,
+    pub summary: Option<GameSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminGameDetails {
+    pub game: AdminGameInfo,
+    pub state: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminQueueInfo {
+    pub game_type: String,
+    pub game_id: GameId,
+    pub created_at: u64,
+    pub player_ids: Vec<PlayerId>,
+    pub status: Option<QueueStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSessionInfo {
+    pub user_id: UserId,
+    pub username: String,
+    pub game_id: GameId,
+    pub game_type: String,
+    pub status: String,
+    pub joined_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminActionRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClearSessionsResponse {
+    pub cleared: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PermissionRequest {
+    pub permission: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminPermissionsResponse {
+    pub admin_id: UserId,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAdminRequest {
+    pub email: String,
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    pub admin_id: Option<UserId>,
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditLogEntry>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserSearchQuery {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub locked: Option<bool>,
+    pub active: Option<bool>,
+    pub verified: Option<bool>,
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_login_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_login_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub min_games_played: Option<i32>,
+    pub max_games_played: Option<i32>,
+    #[serde(default)]
+    pub sort: UserSortField,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub cursor: Option<UserId>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserSearchPage {
+    pub users: Vec<AdminUserView>,
+    pub next_cursor: Option<UserId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBanRequest {
+    pub scope: BanScope,
+    pub reason: String,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration_minutes: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileReportRequest {
+    pub game_id: GameId,
+    pub reported_user_id: UserId,
+    pub category: ReportCategory,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileReportResponse {
+    pub success: bool,
+    pub report_id: uuid::Uuid,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    pub status: Option<ReportStatus>,
+    pub category: Option<ReportCategory>,
+    pub reporter_id: Option<UserId>,
+    pub reported_user_id: Option<UserId>,
+    pub game_id: Option<GameId>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportPage {
+    pub reports: Vec<PlayerReport>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportBanResponse {
+    pub report: PlayerReport,
+    pub ban: UserBan,
+}
@@ -0,0 +1,254 @@
+use axum::{extract::State, http::StatusCode, response::Json, Extension};
+
+use super::{get_token, models::*};
+use crate::{
+    auth::{authorize_user, validate_token, Claims},
+    core::{get_game_registry, ActiveGameInfo, GameRegistry},
+    database::{NewPlayerReport, ReportRepository, UserRepository},
+    models::GameSummary,
+    prelude::*,
+};
+
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+fn report_error(status: StatusCode, message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            success: false,
+            message: message.to_string(),
+        }),
+    )
+}
+
+/// Captures what moderators need to judge the report: the table as it stands and the recent
+/// hand history of both players. Parts the game fails to provide are left empty rather than
+/// holding up the report.
+async fn capture_context(
+    registry: &GameRegistry,
+    game: &ActiveGameInfo,
+    reporter_id: UserId,
+    reported_user_id: UserId,
+) -> Value {
+    let summary: Option<GameSummary> = match registry.get_game_summary(game.id).await {
+        Ok(summary) => Some(summary),
+        Err(err) => {
+            eprintln!("Failed to capture the summary of game {}: {err}", game.id);
+            None
+        }
+    };
+    let mut histories: Vec<Vec<GameMessage>> = Vec::new();
+    for player_id in [reporter_id, reported_user_id] {
+        match registry.get_player_history(game.id, player_id).await {
+            Ok(history) => histories.push(history),
+            Err(err) => {
+                eprintln!(
+                    "Failed to capture the history of player {player_id} in game {}: {err}",
+                    game.id
+                );
+                histories.push(Vec::new());
+            }
+        }
+    }
+    serde_json::json!({
+        "captured_at": chrono::Utc::now(),
+        "summary": summary,
+        "hand_history": {
+            "reporter": histories[0],
+            "reported": histories[1],
+        },
+    })
+}
+
+/// Checks that a report is short enough and names another player at the reporter's running
+/// table, returning that table.
+fn validate_report(
+    payload: &FileReportRequest,
+    reporter_id: UserId,
+    game: Option<ActiveGameInfo>,
+) -> Result<ActiveGameInfo, (StatusCode, Json<ErrorResponse>)> {
+    if payload.description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(report_error(
+            StatusCode::BAD_REQUEST,
+            &format!("Description must be at most {MAX_DESCRIPTION_LENGTH} characters"),
+        ));
+    }
+    if payload.reported_user_id == reporter_id {
+        return Err(report_error(
+            StatusCode::BAD_REQUEST,
+            "You cannot report yourself",
+        ));
+    }
+    let game: ActiveGameInfo =
+        game.ok_or_else(|| report_error(StatusCode::NOT_FOUND, "Game is not running"))?;
+    if !game.player_ids.contains(&reporter_id) {
+        return Err(report_error(
+            StatusCode::FORBIDDEN,
+            "You are not playing in this game",
+        ));
+    }
+    if !game.player_ids.contains(&payload.reported_user_id) {
+        return Err(report_error(
+            StatusCode::BAD_REQUEST,
+            "The reported player is not in this game",
+        ));
+    }
+    Ok(game)
+}
+
+/// Lets a player report another player at their table. Reports can only be filed while the
+/// game is running, since that is when its hand history is still available.
+pub async fn file_report(
+    headers: axum::http::HeaderMap,
+    State(user_repo): State<UserRepository>,
+    Extension(report_repo): Extension<ReportRepository>,
+    Json(payload): Json<FileReportRequest>,
+) -> Result<Json<FileReportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let token: &str = get_token(&headers).ok_or_else(|| {
+        report_error(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid authorization header",
+        )
+    })?;
+    let claims: Claims = validate_token(token)
+        .map_err(|_| report_error(StatusCode::UNAUTHORIZED, "Invalid token"))?;
+    match authorize_user(&user_repo, &claims).await {
+        Ok(Some(_)) => {}
+        _ => return Err(report_error(StatusCode::UNAUTHORIZED, "User not found")),
+    }
+    let registry: &GameRegistry = get_game_registry();
+    let game: ActiveGameInfo = validate_report(
+        &payload,
+        claims.sub,
+        registry.get_active_game_info(payload.game_id).await,
+    )?;
+    let report: NewPlayerReport = NewPlayerReport {
+        game_id: game.id,
+        game_type: game.game_type.clone(),
+        reporter_id: claims.sub,
+        reported_user_id: payload.reported_user_id,
+        category: payload.category,
+        description: payload.description.trim().to_string(),
+        context: capture_context(registry, &game, claims.sub, payload.reported_user_id).await,
+    };
+    match report_repo.create_report(&report).await {
+        Ok(Some(report)) => {
+            println!(
+                "User {} reported {} in game {} for {}",
+                claims.username, report.reported_user_id, report.game_id, report.category
+            );
+            Ok(Json(FileReportResponse {
+                success: true,
+                report_id: report.id,
+                message: "Report received, thank you".to_string(),
+            }))
+        }
+        Ok(None) => Err(report_error(
+            StatusCode::CONFLICT,
+            "You already have an open report against this player in this game",
+        )),
+        Err(err) => Err(report_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to file report: {err}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ReportCategory;
+    use std::time::SystemTime;
+
+    fn table(player_ids: &[PlayerId]) -> ActiveGameInfo {
+        ActiveGameInfo {
+            id: GameId::new_v4(),
+            game_type: "Qafoon".to_string(),
+            created_at: SystemTime::now(),
+            started_at: SystemTime::now(),
+            player_ids: player_ids.to_vec(),
+        }
+    }
+
+    fn report(
+        game: &ActiveGameInfo,
+        reported_user_id: UserId,
+        description: &str,
+    ) -> FileReportRequest {
+        FileReportRequest {
+            game_id: game.id,
+            reported_user_id,
+            category: ReportCategory::Abuse,
+            description: description.to_string(),
+        }
+    }
+
+    fn rejection(result: Result<ActiveGameInfo, (StatusCode, Json<ErrorResponse>)>) -> StatusCode {
+        match result {
+            Ok(_) => panic!("Expected the report to be rejected"),
+            Err((status, _)) => status,
+        }
+    }
+
+    #[test]
+    fn reports_against_a_tablemate_are_accepted() {
+        let (reporter, reported): (UserId, UserId) = (UserId::new_v4(), UserId::new_v4());
+        let game: ActiveGameInfo = table(&[reporter, reported]);
+        let description: String = "x".repeat(MAX_DESCRIPTION_LENGTH);
+        let accepted: ActiveGameInfo = validate_report(
+            &report(&game, reported, &description),
+            reporter,
+            Some(game.clone()),
+        )
+        .unwrap();
+        assert_eq!(accepted.id, game.id);
+    }
+
+    #[test]
+    fn invalid_reports_are_rejected() {
+        let (reporter, reported, stranger): (UserId, UserId, UserId) =
+            (UserId::new_v4(), UserId::new_v4(), UserId::new_v4());
+        let game: ActiveGameInfo = table(&[reporter, reported]);
+        let too_long: String = "é".repeat(MAX_DESCRIPTION_LENGTH + 1);
+        assert_eq!(
+            rejection(validate_report(
+                &report(&game, reported, &too_long),
+                reporter,
+                Some(game.clone())
+            )),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            rejection(validate_report(
+                &report(&game, reporter, ""),
+                reporter,
+                Some(game.clone())
+            )),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            rejection(validate_report(
+                &report(&game, reported, ""),
+                reporter,
+                None
+            )),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            rejection(validate_report(
+                &report(&game, reported, ""),
+                stranger,
+                Some(game.clone())
+            )),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            rejection(validate_report(
+                &report(&game, stranger, ""),
+                reporter,
+                Some(game)
+            )),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
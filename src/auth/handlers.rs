@@ -1,6 +1,11 @@
-use super::verify_password;
+use chrono::{Duration, TimeDelta, Utc};
+
+use super::{
+    generate_refresh_token, generate_user_access_token, hash_refresh_token, verify_password,
+    Claims, Permission, TokenPair, UserTokens,
+};
 use crate::{
-    database::{Admin, User},
+    database::{Admin, AdminRepository, BanScope, RefreshToken, User, UserRepository},
     prelude::*,
 };
 
@@ -22,10 +27,140 @@ pub async fn login_user(
     if user.is_locked {
         return Ok(None);
     }
+    if let Some(ban) = user_repository
+        .get_active_ban(user.id, BanScope::Login)
+        .await?
+    {
+        return Err(Error::Banned(ban.notice()));
+    }
     user_repository.update_last_login(user.id).await?;
     Ok(Some(user))
 }
 
+/// Issues a player's access token and a refresh token, starting a new token family unless
+/// `family_id` continues one that is being rotated.
+pub async fn issue_user_tokens(
+    user_repository: &UserRepository,
+    user: &User,
+    family_id: Option<uuid::Uuid>,
+) -> Result<UserTokens> {
+    let refresh_expire_time: TimeDelta =
+        Duration::hours(get_config().jwt.refresh_expire_time.into());
+    let token_id: uuid::Uuid = uuid::Uuid::new_v4();
+    let refresh_token: String = generate_refresh_token();
+    user_repository
+        .create_refresh_token(
+            token_id,
+            family_id.unwrap_or_else(uuid::Uuid::new_v4),
+            user.id,
+            &hash_refresh_token(&refresh_token),
+            (Utc::now() + refresh_expire_time).naive_utc(),
+        )
+        .await?;
+    let access: TokenPair = generate_user_access_token(user.id, user.username.clone(), token_id)
+        .map_err(|err| Error::Other(format!("Failed to generate access token: {err}")))?;
+    Ok(UserTokens {
+        access,
+        refresh_token,
+        refresh_expires_in: refresh_expire_time.num_seconds(),
+    })
+}
+
+/// Trades a refresh token for a new pair in the same family. A token presented twice has
+/// leaked, so its whole family is revoked and the caller must log in again.
+pub async fn refresh_user_tokens(
+    user_repository: &UserRepository,
+    refresh_token: &str,
+) -> Result<Option<(User, UserTokens)>> {
+    let stored: RefreshToken = match user_repository
+        .get_refresh_token_by_hash(&hash_refresh_token(refresh_token))
+        .await?
+    {
+        Some(stored) if stored.revoked_at.is_none() => stored,
+        _ => return Ok(None),
+    };
+    if stored.expires_at <= Utc::now().naive_utc() {
+        return Ok(None);
+    }
+    if stored.used_at.is_some() || !user_repository.use_refresh_token(stored.id).await? {
+        println!(
+            "Refresh token reuse detected for user {}, revoking its token family",
+            stored.user_id
+        );
+        user_repository
+            .revoke_token_family(stored.family_id)
+            .await?;
+        return Ok(None);
+    }
+    let user: User = match user_repository.get_user_by_id(stored.user_id).await? {
+        Some(user) if !user.is_locked => user,
+        _ => {
+            user_repository
+                .revoke_token_family(stored.family_id)
+                .await?;
+            return Ok(None);
+        }
+    };
+    if let Some(ban) = user_repository
+        .get_active_ban(user.id, BanScope::Login)
+        .await?
+    {
+        user_repository
+            .revoke_token_family(stored.family_id)
+            .await?;
+        return Err(Error::Banned(ban.notice()));
+    }
+    let tokens: UserTokens =
+        issue_user_tokens(user_repository, &user, Some(stored.family_id)).await?;
+    Ok(Some((user, tokens)))
+}
+
+/// Revokes the token family of a refresh token. Returns `false` for unknown tokens.
+pub async fn logout_user(user_repository: &UserRepository, refresh_token: &str) -> Result<bool> {
+    match user_repository
+        .get_refresh_token_by_hash(&hash_refresh_token(refresh_token))
+        .await?
+    {
+        Some(stored) => {
+            user_repository
+                .revoke_token_family(stored.family_id)
+                .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Resolves the player behind an access token. Tokens of deleted or locked users, tokens
+/// whose refresh token family was revoked and tokens not bound to a refresh token resolve
+/// to no user.
+pub async fn authorize_user(
+    user_repository: &UserRepository,
+    claims: &Claims,
+) -> Result<Option<User>> {
+    match claims.jti {
+        Some(jti) => authorize_session(user_repository, claims.sub, jti).await,
+        None => Ok(None),
+    }
+}
+
+/// Resolves the player behind any token derived from their login, such as the game session
+/// and reconnection tokens checked by the game server.
+pub async fn authorize_session(
+    user_repository: &UserRepository,
+    user_id: UserId,
+    jti: uuid::Uuid,
+) -> Result<Option<User>> {
+    let user: User = match user_repository.get_user_by_id(user_id).await? {
+        Some(user) if !user.is_locked => user,
+        _ => return Ok(None),
+    };
+    if user_repository.is_refresh_token_revoked(jti).await? {
+        return Ok(None);
+    }
+    Ok(Some(user))
+}
+
 pub async fn register_user(
     user_repository: &crate::database::UserRepository,
     email: String,
@@ -65,3 +200,252 @@ pub async fn login_admin(
         Ok(None)
     }
 }
+
+pub async fn create_admin(
+    admin_repo: &AdminRepository,
+    email: String,
+    username: String,
+    password: String,
+    permissions: Vec<String>,
+) -> Result<Option<Admin>> {
+    super::validate_email(&email)?;
+    super::validate_username(&username)?;
+    super::validate_password(&password)?;
+    for permission in &permissions {
+        permission.parse::<Permission>()?;
+    }
+    if admin_repo.email_exists(&email).await? || admin_repo.username_exists(&username).await? {
+        return Ok(None);
+    }
+    let password_hash: String = super::hash_password(&password)?;
+    let admin: Admin = admin_repo
+        .create_admin(&email, &username, &password_hash, &permissions)
+        .await?;
+    Ok(Some(admin))
+}
+
+pub async fn reset_admin_password(
+    admin_repo: &AdminRepository,
+    admin_id: UserId,
+    password: String,
+) -> Result<()> {
+    super::validate_password(&password)?;
+    let password_hash: String = super::hash_password(&password)?;
+    admin_repo.update_password(admin_id, &password_hash).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::init_test_config;
+
+    async fn player(user_repository: &UserRepository) -> User {
+        init_test_config();
+        user_repository
+            .create_user("player@example.com", "player", "not-a-real-hash")
+            .await
+            .unwrap()
+    }
+
+    async fn is_revoked(user_repository: &UserRepository, refresh_token: &str) -> bool {
+        user_repository
+            .get_refresh_token_by_hash(&hash_refresh_token(refresh_token))
+            .await
+            .unwrap()
+            .is_some_and(|stored: RefreshToken| stored.revoked_at.is_some())
+    }
+
+    #[sqlx::test]
+    async fn refresh_rotates_tokens_within_their_family(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        let user: User = player(&user_repository).await;
+        let issued: UserTokens = issue_user_tokens(&user_repository, &user, None)
+            .await
+            .unwrap();
+        let (refreshed_user, rotated): (User, UserTokens) =
+            refresh_user_tokens(&user_repository, &issued.refresh_token)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(refreshed_user.id, user.id);
+        assert_ne!(rotated.refresh_token, issued.refresh_token);
+        let first: RefreshToken = user_repository
+            .get_refresh_token_by_hash(&hash_refresh_token(&issued.refresh_token))
+            .await
+            .unwrap()
+            .unwrap();
+        let second: RefreshToken = user_repository
+            .get_refresh_token_by_hash(&hash_refresh_token(&rotated.refresh_token))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.family_id, second.family_id);
+        assert!(first.used_at.is_some());
+        assert!(second.used_at.is_none() && second.revoked_at.is_none());
+    }
+
+    #[sqlx::test]
+    async fn reusing_a_rotated_token_revokes_its_family(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        let user: User = player(&user_repository).await;
+        let issued: UserTokens = issue_user_tokens(&user_repository, &user, None)
+            .await
+            .unwrap();
+        let (_, rotated): (User, UserTokens) =
+            refresh_user_tokens(&user_repository, &issued.refresh_token)
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(refresh_user_tokens(&user_repository, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(is_revoked(&user_repository, &rotated.refresh_token).await);
+        assert!(
+            refresh_user_tokens(&user_repository, &rotated.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[sqlx::test]
+    async fn revoked_tokens_cannot_be_refreshed(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        let user: User = player(&user_repository).await;
+        let issued: UserTokens = issue_user_tokens(&user_repository, &user, None)
+            .await
+            .unwrap();
+        let stored: RefreshToken = user_repository
+            .get_refresh_token_by_hash(&hash_refresh_token(&issued.refresh_token))
+            .await
+            .unwrap()
+            .unwrap();
+        user_repository
+            .revoke_token_family(stored.family_id)
+            .await
+            .unwrap();
+        assert!(refresh_user_tokens(&user_repository, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(authorize_session(&user_repository, user.id, stored.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn logout_revokes_the_whole_family(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        let user: User = player(&user_repository).await;
+        let issued: UserTokens = issue_user_tokens(&user_repository, &user, None)
+            .await
+            .unwrap();
+        let (_, rotated): (User, UserTokens) =
+            refresh_user_tokens(&user_repository, &issued.refresh_token)
+                .await
+                .unwrap()
+                .unwrap();
+        let current: RefreshToken = user_repository
+            .get_refresh_token_by_hash(&hash_refresh_token(&rotated.refresh_token))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(authorize_session(&user_repository, user.id, current.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(logout_user(&user_repository, &issued.refresh_token)
+            .await
+            .unwrap());
+        assert!(is_revoked(&user_repository, &rotated.refresh_token).await);
+        assert!(authorize_session(&user_repository, user.id, current.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(
+            refresh_user_tokens(&user_repository, &rotated.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(!logout_user(&user_repository, "unknown-token")
+            .await
+            .unwrap());
+    }
+
+    #[sqlx::test]
+    async fn admins_are_created_once_with_valid_permissions(pool: PgPool) {
+        let admin_repository: AdminRepository = AdminRepository::new(pool);
+        let admin: Admin = create_admin(
+            &admin_repository,
+            "root@example.com".to_string(),
+            "root".to_string(),
+            "Secret123".to_string(),
+            vec!["users:read".to_string()],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(admin.permissions, vec!["users:read"]);
+        assert!(create_admin(
+            &admin_repository,
+            "root@example.com".to_string(),
+            "other".to_string(),
+            "Secret123".to_string(),
+            Vec::new(),
+        )
+        .await
+        .unwrap()
+        .is_none());
+        assert!(create_admin(
+            &admin_repository,
+            "ops@example.com".to_string(),
+            "ops".to_string(),
+            "Secret123".to_string(),
+            vec!["games:everything".to_string()],
+        )
+        .await
+        .is_err());
+    }
+
+    #[sqlx::test]
+    async fn reset_passwords_replace_the_old_one(pool: PgPool) {
+        let admin_repository: AdminRepository = AdminRepository::new(pool);
+        let admin: Admin = create_admin(
+            &admin_repository,
+            "root@example.com".to_string(),
+            "root".to_string(),
+            "Secret123".to_string(),
+            Vec::new(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(
+            reset_admin_password(&admin_repository, admin.id, "short".to_string())
+                .await
+                .is_err()
+        );
+        reset_admin_password(&admin_repository, admin.id, "Changed456".to_string())
+            .await
+            .unwrap();
+        assert!(login_admin(
+            &admin_repository,
+            "root".to_string(),
+            "Secret123".to_string()
+        )
+        .await
+        .unwrap()
+        .is_none());
+        assert!(login_admin(
+            &admin_repository,
+            "root".to_string(),
+            "Changed456".to_string()
+        )
+        .await
+        .unwrap()
+        .is_some());
+    }
+}
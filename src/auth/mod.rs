@@ -1,11 +1,13 @@
 pub mod handlers;
 pub mod models;
 pub mod password;
+pub mod permissions;
 pub mod token;
 pub mod validators;
 
 pub use handlers::*;
 pub use models::*;
 pub use password::*;
+pub use permissions::*;
 pub use token::*;
 pub use validators::*;
@@ -8,11 +8,23 @@ pub struct TokenPair {
     pub expires_in: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A player's access token together with the refresh token that renews it.
+#[derive(Debug)]
+pub struct UserTokens {
+    pub access: TokenPair,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: UserId,
     pub username: String,
     pub is_admin: bool,
+    /// The refresh token issued alongside a player's access token. Revoking its family
+    /// revokes the access token too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<uuid::Uuid>,
     pub exp: usize,
     pub iat: usize,
 }
@@ -22,6 +34,9 @@ pub struct GameSessionClaims {
     pub sub: UserId,
     pub username: String,
     pub game_choice: String,
+    /// The refresh token behind the access token this was issued for, so the game server
+    /// can refuse tokens from a revoked login.
+    pub jti: uuid::Uuid,
     pub exp: usize,
     pub iat: usize,
 }
@@ -30,6 +45,7 @@ pub struct GameSessionClaims {
 pub struct ReconnectClaims {
     pub sub: UserId,
     pub game_id: GameId,
+    pub jti: uuid::Uuid,
     pub exp: usize,
     pub iat: usize,
 }
@@ -0,0 +1,73 @@
+use std::{fmt, str::FromStr};
+
+use crate::prelude::*;
+
+/// Actions an admin can be granted. They are stored as strings in `admins.permissions`
+/// and copied into the admin token at login.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    UsersRead,
+    UsersLock,
+    UsersDelete,
+    GamesManage,
+    AdminsManage,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 5] = [
+        Permission::UsersRead,
+        Permission::UsersLock,
+        Permission::UsersDelete,
+        Permission::GamesManage,
+        Permission::AdminsManage,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::UsersRead => "users:read",
+            Permission::UsersLock => "users:lock",
+            Permission::UsersDelete => "users:delete",
+            Permission::GamesManage => "games:manage",
+            Permission::AdminsManage => "admins:manage",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission: &Permission| permission.as_str() == value)
+            .ok_or_else(|| Error::Validator(format!("Unknown permission '{value}'")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_round_trip_through_strings() {
+        for permission in Permission::ALL {
+            assert_eq!(
+                permission.to_string().parse::<Permission>().ok(),
+                Some(permission)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_permissions_are_rejected() {
+        assert!("users:write".parse::<Permission>().is_err());
+        assert!("USERS:READ".parse::<Permission>().is_err());
+        assert!("".parse::<Permission>().is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Duration, TimeDelta, Utc};
 use jsonwebtoken::errors::Error as JsonWebTokenError;
+use sha2::{Digest, Sha256};
 
 use super::{Claims, GameSessionClaims, SessionTokenType, TokenPair};
 use crate::{
@@ -12,14 +13,34 @@ pub fn generate_token(
     username: String,
     is_admin: bool,
 ) -> Result<TokenPair, JsonWebTokenError> {
-    let config: &Config = get_config();
+    let expire_time: TimeDelta = Duration::hours(get_config().jwt.expire_time.into());
+    sign_access_token(user_id, username, is_admin, None, expire_time)
+}
+
+/// Issues a short-lived player access token bound to the refresh token `jti`.
+pub fn generate_user_access_token(
+    user_id: UserId,
+    username: String,
+    jti: uuid::Uuid,
+) -> Result<TokenPair, JsonWebTokenError> {
+    let expire_time: TimeDelta = Duration::seconds(get_config().jwt.access_expire_time.into());
+    sign_access_token(user_id, username, false, Some(jti), expire_time)
+}
+
+fn sign_access_token(
+    user_id: UserId,
+    username: String,
+    is_admin: bool,
+    jti: Option<uuid::Uuid>,
+    expire_time: TimeDelta,
+) -> Result<TokenPair, JsonWebTokenError> {
     let now: DateTime<Utc> = Utc::now();
-    let expire_time: TimeDelta = Duration::hours(config.jwt.expire_time.into());
     let expires_at: DateTime<Utc> = now + expire_time;
     let claims: Claims = Claims {
         sub: user_id,
         username,
         is_admin,
+        jti,
         exp: expires_at.timestamp() as usize,
         iat: now.timestamp() as usize,
     };
@@ -30,10 +51,20 @@ pub fn generate_token(
     })
 }
 
+/// Returns a new opaque refresh token. Only its hash is ever stored.
+pub fn generate_refresh_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+pub fn hash_refresh_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
 pub fn generate_game_session_token(
     user_id: UserId,
     username: String,
     game_choice: String,
+    jti: uuid::Uuid,
 ) -> Result<TokenPair, JsonWebTokenError> {
     let config: &Config = get_config();
     let now: DateTime<Utc> = Utc::now();
@@ -43,6 +74,7 @@ pub fn generate_game_session_token(
         sub: user_id,
         username,
         game_choice,
+        jti,
         exp: expires_at.timestamp() as usize,
         iat: now.timestamp() as usize,
     };
@@ -56,14 +88,16 @@ pub fn generate_game_session_token(
 pub fn generate_reconnection_token(
     player_id: PlayerId,
     game_id: GameId,
+    jti: uuid::Uuid,
 ) -> Result<TokenPair, JsonWebTokenError> {
     let config: &Config = get_config();
     let now: DateTime<Utc> = Utc::now();
-    let expire_time: TimeDelta = Duration::seconds(config.jwt.expire_time.into());
+    let expire_time: TimeDelta = Duration::seconds(config.jwt.reconnection_expire_time.into());
     let expires_at: DateTime<Utc> = now + expire_time;
     let claims: ReconnectClaims = ReconnectClaims {
         sub: player_id,
         game_id,
+        jti,
         exp: expires_at.timestamp() as usize,
         iat: now.timestamp() as usize,
     };
@@ -104,3 +138,54 @@ pub fn identify_and_decode_token(token: &str) -> crate::core::Result<SessionToke
         "failed to identify game server token".to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::init_test_config;
+
+    #[test]
+    fn reconnection_tokens_use_their_own_lifetime() {
+        let config: &Config = init_test_config();
+        let (player_id, game_id, jti): (PlayerId, GameId, uuid::Uuid) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let token: TokenPair = generate_reconnection_token(player_id, game_id, jti).unwrap();
+        assert_eq!(
+            token.expires_in,
+            i64::from(config.jwt.reconnection_expire_time)
+        );
+        match identify_and_decode_token(&token.access_token).unwrap() {
+            SessionTokenType::Reconnection(claims) => {
+                assert_eq!(
+                    (claims.sub, claims.game_id, claims.jti),
+                    (player_id, game_id, jti)
+                );
+                assert_eq!(
+                    claims.exp - claims.iat,
+                    config.jwt.reconnection_expire_time as usize
+                );
+            }
+            other => panic!("Expected a reconnection token, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn game_session_tokens_are_told_apart_from_reconnection_tokens() {
+        init_test_config();
+        let token: TokenPair = generate_game_session_token(
+            uuid::Uuid::new_v4(),
+            "player".to_string(),
+            "Qafoon".to_string(),
+            uuid::Uuid::new_v4(),
+        )
+        .unwrap();
+        assert!(matches!(
+            identify_and_decode_token(&token.access_token).unwrap(),
+            SessionTokenType::GameSession(_)
+        ));
+        assert!(identify_and_decode_token("not-a-token").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Command line tool for bootstrapping and maintaining admin accounts.
+//!
+//! Passwords stay out of the shell history: they are prompted for without echo, read from
+//! `CARDS_ADMIN_PASSWORD`, or read from standard input when it is piped.
+
+use std::io::{BufRead, IsTerminal};
+
+use cards::{
+    auth::{create_admin, reset_admin_password, Permission},
+    config,
+    core::types::Result,
+    database::{self, Admin, AdminRepository},
+    errors::Error,
+};
+
+const USAGE: &str = "Usage:
+    cards-admin migrate
+    cards-admin create-admin <email> <username> [permission ...]
+    cards-admin reset-password <username>
+    cards-admin grant-permission <username> <permission ...>
+    cards-admin list-admins
+
+Admins created without explicit permissions receive all of them.
+Set CARDS_ADMIN_PASSWORD or pipe the password to skip the prompt.
+Permissions: users:read, users:lock, users:delete, games:manage, admins:manage";
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run(&args).await {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let Some(command) = args.first() else {
+        return Err(Error::Other(USAGE.to_string()));
+    };
+    config::init_config()?;
+    let pool: sqlx::PgPool = database::create_database_pool().await?;
+    let admin_repo: AdminRepository = AdminRepository::new(pool.clone());
+    match (command.as_str(), &args[1..]) {
+        ("migrate", []) => database::run_migrations(&pool).await,
+        ("create-admin", [email, username, permissions @ ..]) => {
+            let permissions: Vec<String> = if permissions.is_empty() {
+                Permission::ALL
+                    .iter()
+                    .map(|permission: &Permission| permission.to_string())
+                    .collect()
+            } else {
+                permissions.to_vec()
+            };
+            let password: String = read_password()?;
+            match create_admin(
+                &admin_repo,
+                email.clone(),
+                username.clone(),
+                password,
+                permissions,
+            )
+            .await?
+            {
+                Some(admin) => {
+                    println!("Created admin {} ({})", admin.username, admin.id);
+                    Ok(())
+                }
+                None => Err(Error::Other(format!(
+                    "An admin with email {email} or username {username} already exists"
+                ))),
+            }
+        }
+        ("reset-password", [username]) => {
+            let admin: Admin = admin_repo
+                .get_admin_by_username(username.clone())
+                .await?
+                .ok_or_else(|| Error::Other(format!("No active admin named {username}")))?;
+            let password: String = read_password()?;
+            reset_admin_password(&admin_repo, admin.id, password).await?;
+            println!("Password of admin {username} was reset");
+            Ok(())
+        }
+        ("grant-permission", [username, permissions @ ..]) if !permissions.is_empty() => {
+            let permissions: Vec<Permission> = permissions
+                .iter()
+                .map(|permission: &String| permission.parse())
+                .collect::<Result<Vec<Permission>>>()?;
+            let admin: Admin = admin_repo
+                .get_admin_by_username(username.clone())
+                .await?
+                .ok_or_else(|| Error::Other(format!("No active admin named {username}")))?;
+            let mut granted: Vec<String> = admin.permissions;
+            for permission in permissions {
+                granted = admin_repo
+                    .grant_permission(admin.id, permission.as_str())
+                    .await?;
+            }
+            println!("Admin {username} now has {}", granted.join(","));
+            Ok(())
+        }
+        ("list-admins", []) => {
+            for admin in admin_repo.list_admins().await? {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    admin.id,
+                    admin.username,
+                    admin.email,
+                    if admin.is_active {
+                        "active"
+                    } else {
+                        "inactive"
+                    },
+                    admin.permissions.join(",")
+                );
+            }
+            Ok(())
+        }
+        _ => Err(Error::Other(USAGE.to_string())),
+    }
+}
+
+fn read_password() -> Result<String> {
+    if let Ok(password) = std::env::var("CARDS_ADMIN_PASSWORD") {
+        return Ok(password);
+    }
+    if std::io::stdin().is_terminal() {
+        return rpassword::prompt_password("Password: ")
+            .map_err(|err: std::io::Error| Error::Other(err.to_string()));
+    }
+    let mut password: String = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut password)
+        .map_err(|err: std::io::Error| Error::Other(err.to_string()))?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
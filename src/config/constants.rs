@@ -3,16 +3,32 @@ pub const DEFAULT_GAME_SERVER_PORT: &str = "0";
 pub const DEFAULT_TLS_CERTS_PATH: &str = ".";
 pub const DEFAULT_PLAYER_CHOICE_TIMEOUT_ENABLED: bool = true;
 pub const DEFAULT_QUEUE_CLEAN_UP_INTERVAL: u64 = 300;
+pub const DEFAULT_REPLAY_BUFFER_SIZE: usize = 256;
+pub const DEFAULT_GAME_SNAPSHOT_ENABLED: bool = true;
+pub const DEFAULT_GAME_SNAPSHOT_PATH: &str = "snapshots";
 pub const DEFAULT_TEAM_SELECTION_TIMEOUT: u64 = 300;
 pub const DEFAULT_PLAYER_CHOICE_TIMEOUT: u64 = 30;
 pub const DEFAULT_QUEUE_CUTOFF_TIMEOUT: u64 = 600;
 pub const DEFAULT_GAME_DURATION_TIMEOUT: u64 = 10800;
 pub const DEFAULT_PLAYER_RECONNECTION_TIMEOUT: u64 = 60;
 pub const DEFAULT_PLAYER_RECONNECTION_MAX_RETRIES: u64 = 3;
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT: u64 = 300;
+pub const DEFAULT_CLOCK_MODE_ENABLED: bool = false;
+pub const DEFAULT_CLOCK_TIME_BANK: u64 = 300;
+pub const DEFAULT_CLOCK_INCREMENT: u64 = 5;
+pub const DEFAULT_CLOCK_TIMEOUT_PENALTY: &str = "end_game";
+pub const DEFAULT_CLOCK_PENALTY_POINTS: usize = 10;
+pub const DEFAULT_TIMEOUT_POLICY: &str = "auto_play";
+pub const DEFAULT_TIMEOUT_BOT_TAKEOVER_AFTER: usize = 2;
+pub const DEFAULT_TIMEOUT_END_GAME_AFTER: usize = 10;
 pub const DEFAULT_DATABASE_URL: &str = "postgresql://localhost:5432/cards_game";
 pub const DEFAULT_DATABASE_MAX_CONNECTIONS: &str = "10";
 pub const DEFAULT_DATABASE_MIN_CONNECTIONS: &str = "2";
 pub const DEFAULT_JWT_SECRET: &str = "abababababababababababababababab";
 pub const DEFAULT_JWT_EXPIRE_DURATION: &str = "24";
+pub const DEFAULT_JWT_RECONNECTION_EXPIRE_DURATION: &str = "3600";
+pub const DEFAULT_JWT_ACCESS_EXPIRE_DURATION: &str = "900";
+pub const DEFAULT_JWT_REFRESH_EXPIRE_DURATION: &str = "720";
 pub const DEFAULT_API_SERVER_HOST: &str = "127.0.0.1";
 pub const DEFAULT_API_SERVER_PORT: &str = "0";
+pub const DEFAULT_API_TRUSTED_PROXIES: &str = "";
@@ -2,5 +2,5 @@ pub mod constants;
 pub mod models;
 pub mod settings;
 
-pub use models::Config;
+pub use models::{Config, TimeoutAction, TimeoutPenalty, TimeoutPolicyConfig};
 pub use settings::{get_config, init_config};
@@ -1,10 +1,12 @@
-use std::{path::PathBuf, time::Duration};
+use std::{net::IpAddr, path::PathBuf, time::Duration};
 
 #[derive(Debug)]
 pub struct Config {
     pub game_server: GameServerConfig,
     pub tls: TlsConfig,
     pub timeout: TimeoutConfig,
+    pub clock: ClockConfig,
+    pub timeout_policy: TimeoutPolicyConfig,
     pub database: DatabaseConfig,
     pub jwt: JwtConfig,
     pub api_server: ApiServerConfig,
@@ -14,7 +16,13 @@ pub struct Config {
 pub struct GameServerConfig {
     pub host: String,
     pub port: u16,
+    /// The address clients are told to connect to, which differs from the bind address
+    /// behind a proxy or when binding to every interface.
+    pub public_address: String,
     pub queue_clean_up_interval: Duration,
+    pub replay_buffer_size: usize,
+    pub snapshot_enabled: bool,
+    pub snapshot_path: PathBuf,
 }
 
 #[derive(Debug)]
@@ -32,6 +40,61 @@ pub struct TimeoutConfig {
     pub game_duration: Duration,
     pub player_reconnection: Duration,
     pub player_reconnection_max_retries: usize,
+    pub shutdown_drain: Duration,
+}
+
+#[derive(Debug)]
+pub struct ClockConfig {
+    pub enabled: bool,
+    pub time_bank: Duration,
+    pub increment: Duration,
+    pub penalty: TimeoutPenalty,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeoutPenalty {
+    EndGame,
+    Forfeit,
+    ScoreDeduction(usize),
+}
+
+impl std::fmt::Display for TimeoutPenalty {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TimeoutPenalty::EndGame => write!(f, "game cancelled"),
+            TimeoutPenalty::Forfeit => write!(f, "forfeit"),
+            TimeoutPenalty::ScoreDeduction(points) => write!(f, "{points} points deducted"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TimeoutPolicyConfig {
+    pub team: TimeoutAction,
+    pub bet: TimeoutAction,
+    pub fold: TimeoutAction,
+    pub hokm: TimeoutAction,
+    pub play_card: TimeoutAction,
+    pub bot_takeover_after: usize,
+    pub end_game_after: usize,
+}
+
+impl TimeoutPolicyConfig {
+    pub fn action_for(&self, decision: &str) -> &TimeoutAction {
+        match decision {
+            "Team" => &self.team,
+            "Bet" => &self.bet,
+            "Fold" => &self.fold,
+            "Hokm" => &self.hokm,
+            _ => &self.play_card,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeoutAction {
+    AutoPlay,
+    EndGame,
 }
 
 #[derive(Debug)]
@@ -45,10 +108,15 @@ pub struct DatabaseConfig {
 pub struct JwtConfig {
     pub secret: String,
     pub expire_time: u32,
+    pub reconnection_expire_time: u32,
+    pub access_expire_time: u32,
+    pub refresh_expire_time: u32,
 }
 
 #[derive(Debug)]
 pub struct ApiServerConfig {
     pub host: String,
     pub port: u16,
+    /// Reverse proxies whose `X-Forwarded-For` header is believed for the client address.
+    pub trusted_proxies: Vec<IpAddr>,
 }
@@ -1,5 +1,5 @@
 use once_cell::sync::OnceCell;
-use std::{env, path::PathBuf, time::Duration};
+use std::{env, net::IpAddr, path::PathBuf, time::Duration};
 
 use super::{constants::*, models::*};
 use crate::prelude::{Error, Result};
@@ -9,18 +9,39 @@ static CONFIG: OnceCell<Config> = OnceCell::new();
 impl Config {
     pub fn from_env() -> Result<Self, Error> {
         dotenv::dotenv().ok();
+        let config: Config = Self::read_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn read_env() -> Result<Self, Error> {
         let tls_path: PathBuf =
             PathBuf::from(env::var("TLS_CERTS_PATH").unwrap_or(DEFAULT_TLS_CERTS_PATH.to_string()));
+        let game_server_host: String =
+            env::var("GAME_SERVER_HOST").unwrap_or(DEFAULT_GAME_SERVER_HOST.to_string());
+        let game_server_port: u16 = env::var("GAME_SERVER_PORT")
+            .unwrap_or(DEFAULT_GAME_SERVER_PORT.to_string())
+            .parse()?;
         let config: Config = Config {
             game_server: GameServerConfig {
-                host: env::var("GAME_SERVER_HOST").unwrap_or(DEFAULT_GAME_SERVER_HOST.to_string()),
-                port: env::var("GAME_SERVER_PORT")
-                    .unwrap_or(DEFAULT_GAME_SERVER_PORT.to_string())
-                    .parse()?,
+                public_address: env::var("GAME_SERVER_PUBLIC_ADDRESS")
+                    .unwrap_or(format!("{game_server_host}:{game_server_port}")),
+                host: game_server_host,
+                port: game_server_port,
                 queue_clean_up_interval: get_env_var_as_duration(
                     "QUEUE_CLEAN_UP_INTERVAL",
                     DEFAULT_QUEUE_CLEAN_UP_INTERVAL,
                 )?,
+                replay_buffer_size: env::var("REPLAY_BUFFER_SIZE")
+                    .unwrap_or(DEFAULT_REPLAY_BUFFER_SIZE.to_string())
+                    .parse()?,
+                snapshot_enabled: env::var("GAME_SNAPSHOT_ENABLED")
+                    .unwrap_or(DEFAULT_GAME_SNAPSHOT_ENABLED.to_string())
+                    .parse()?,
+                snapshot_path: PathBuf::from(
+                    env::var("GAME_SNAPSHOT_PATH")
+                        .unwrap_or(DEFAULT_GAME_SNAPSHOT_PATH.to_string()),
+                ),
             },
             tls: TlsConfig {
                 cert: tls_path.join("cert.pem"),
@@ -53,6 +74,31 @@ impl Config {
                 player_reconnection_max_retries: env::var("PLAYER_RECONNECTION_MAX_RETRIES")
                     .unwrap_or(DEFAULT_PLAYER_RECONNECTION_MAX_RETRIES.to_string())
                     .parse()?,
+                shutdown_drain: get_env_var_as_duration(
+                    "SHUTDOWN_DRAIN_TIMEOUT",
+                    DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
+                )?,
+            },
+            clock: ClockConfig {
+                enabled: env::var("CLOCK_MODE_ENABLED")
+                    .unwrap_or(DEFAULT_CLOCK_MODE_ENABLED.to_string())
+                    .parse()?,
+                time_bank: get_env_var_as_duration("CLOCK_TIME_BANK", DEFAULT_CLOCK_TIME_BANK)?,
+                increment: get_env_var_as_duration("CLOCK_INCREMENT", DEFAULT_CLOCK_INCREMENT)?,
+                penalty: get_timeout_penalty()?,
+            },
+            timeout_policy: TimeoutPolicyConfig {
+                team: get_timeout_action("TIMEOUT_POLICY_TEAM")?,
+                bet: get_timeout_action("TIMEOUT_POLICY_BET")?,
+                fold: get_timeout_action("TIMEOUT_POLICY_FOLD")?,
+                hokm: get_timeout_action("TIMEOUT_POLICY_HOKM")?,
+                play_card: get_timeout_action("TIMEOUT_POLICY_PLAY_CARD")?,
+                bot_takeover_after: env::var("TIMEOUT_BOT_TAKEOVER_AFTER")
+                    .unwrap_or(DEFAULT_TIMEOUT_BOT_TAKEOVER_AFTER.to_string())
+                    .parse()?,
+                end_game_after: env::var("TIMEOUT_END_GAME_AFTER")
+                    .unwrap_or(DEFAULT_TIMEOUT_END_GAME_AFTER.to_string())
+                    .parse()?,
             },
             database: DatabaseConfig {
                 url: env::var("DATABASE_URL").unwrap_or(DEFAULT_DATABASE_URL.to_string()),
@@ -68,15 +114,27 @@ impl Config {
                 expire_time: env::var("JWT_EXPIRE_DURATION")
                     .unwrap_or(DEFAULT_JWT_EXPIRE_DURATION.to_string())
                     .parse()?,
+                reconnection_expire_time: env::var("JWT_RECONNECTION_EXPIRE_DURATION")
+                    .unwrap_or(DEFAULT_JWT_RECONNECTION_EXPIRE_DURATION.to_string())
+                    .parse()?,
+                access_expire_time: env::var("JWT_ACCESS_EXPIRE_DURATION")
+                    .unwrap_or(DEFAULT_JWT_ACCESS_EXPIRE_DURATION.to_string())
+                    .parse()?,
+                refresh_expire_time: env::var("JWT_REFRESH_EXPIRE_DURATION")
+                    .unwrap_or(DEFAULT_JWT_REFRESH_EXPIRE_DURATION.to_string())
+                    .parse()?,
             },
             api_server: ApiServerConfig {
                 host: env::var("API_SERVER_HOST").unwrap_or(DEFAULT_API_SERVER_HOST.to_string()),
                 port: env::var("API_SERVER_PORT")
                     .unwrap_or(DEFAULT_API_SERVER_PORT.to_string())
                     .parse()?,
+                trusted_proxies: parse_trusted_proxies(
+                    &env::var("API_TRUSTED_PROXIES")
+                        .unwrap_or(DEFAULT_API_TRUSTED_PROXIES.to_string()),
+                )?,
             },
         };
-        config.validate()?;
         Ok(config)
     }
 
@@ -88,10 +146,26 @@ impl Config {
         if self.game_server.port == 0 {
             errors.push("Game server port must be greater than 0".to_string());
         }
+        // Hostnames cannot be checked here, but a wildcard or zero port is never reachable.
+        if let Ok(address) = self
+            .game_server
+            .public_address
+            .parse::<std::net::SocketAddr>()
+        {
+            if address.ip().is_unspecified() || address.port() == 0 {
+                errors.push(
+                    "Game server public address must be reachable by clients, set GAME_SERVER_PUBLIC_ADDRESS"
+                        .to_string(),
+                );
+            }
+        }
         if self.game_server.queue_clean_up_interval.is_zero() {
             errors
                 .push("Server queue clean up interval must be greater than 0 seconds".to_string());
         }
+        if self.game_server.replay_buffer_size == 0 {
+            errors.push("Replay buffer size must be greater than 0".to_string());
+        }
         if !self.tls.cert.exists() {
             errors.push(format!("TLS cert.pem was not found: {:?}", self.tls.cert));
         }
@@ -107,12 +181,27 @@ impl Config {
         if self.timeout.queue_cutoff.is_zero() {
             errors.push("queue cutoff timeout must be greater than 0 seconds".to_string());
         }
+        if self.clock.enabled && self.clock.time_bank.is_zero() {
+            errors.push("clock time bank must be greater than 0 seconds".to_string());
+        }
+        if self.timeout_policy.bot_takeover_after == 0 {
+            errors.push("Bot takeover threshold must be greater than 0".to_string());
+        }
         if self.jwt.secret.is_empty() {
             errors.push("Jwt secret cannot be empty".to_string());
         }
         if self.jwt.expire_time == 0 {
             errors.push("Jwt expire time must be greater than 0".to_string());
         }
+        if self.jwt.reconnection_expire_time == 0 {
+            errors.push("Jwt reconnection expire time must be greater than 0".to_string());
+        }
+        if self.jwt.access_expire_time == 0 {
+            errors.push("Jwt access expire time must be greater than 0".to_string());
+        }
+        if self.jwt.refresh_expire_time == 0 {
+            errors.push("Jwt refresh expire time must be greater than 0".to_string());
+        }
         if self.api_server.host.is_empty() {
             errors.push("Api server host cannot be empty".to_string());
         }
@@ -135,6 +224,54 @@ fn get_env_var_as_duration(key: &str, default_value: u64) -> Result<Duration> {
     ))
 }
 
+fn get_timeout_penalty() -> Result<TimeoutPenalty> {
+    parse_timeout_penalty(
+        &env::var("CLOCK_TIMEOUT_PENALTY").unwrap_or(DEFAULT_CLOCK_TIMEOUT_PENALTY.to_string()),
+        &env::var("CLOCK_PENALTY_POINTS").unwrap_or(DEFAULT_CLOCK_PENALTY_POINTS.to_string()),
+    )
+}
+
+fn parse_timeout_penalty(penalty: &str, points: &str) -> Result<TimeoutPenalty> {
+    match penalty {
+        "end_game" => Ok(TimeoutPenalty::EndGame),
+        "forfeit" => Ok(TimeoutPenalty::Forfeit),
+        "score_deduction" => Ok(TimeoutPenalty::ScoreDeduction(points.parse()?)),
+        invalid => Err(Error::Config(vec![format!(
+            "Invalid clock timeout penalty: {invalid}"
+        )])),
+    }
+}
+
+fn get_timeout_action(key: &str) -> Result<TimeoutAction> {
+    parse_timeout_action(
+        key,
+        &env::var(key).unwrap_or(DEFAULT_TIMEOUT_POLICY.to_string()),
+    )
+}
+
+fn parse_timeout_action(key: &str, action: &str) -> Result<TimeoutAction> {
+    match action {
+        "auto_play" => Ok(TimeoutAction::AutoPlay),
+        "end_game" => Ok(TimeoutAction::EndGame),
+        invalid => Err(Error::Config(vec![format!(
+            "Invalid timeout policy for {key}: {invalid}"
+        )])),
+    }
+}
+
+fn parse_trusted_proxies(proxies: &str) -> Result<Vec<IpAddr>> {
+    proxies
+        .split(',')
+        .map(str::trim)
+        .filter(|proxy: &&str| !proxy.is_empty())
+        .map(|proxy: &str| {
+            proxy
+                .parse::<IpAddr>()
+                .map_err(|_| Error::Config(vec![format!("Invalid trusted proxy address: {proxy}")]))
+        })
+        .collect()
+}
+
 pub fn get_config() -> &'static Config {
     CONFIG.get().expect("CONFIG not initialized")
 }
@@ -145,3 +282,117 @@ pub fn init_config() -> Result<(), Error> {
     println!("Initialized configuration successfully");
     Ok(())
 }
+
+/// Initializes the configuration from the defaults and the test environment, skipping the
+/// checks that need a deployment such as TLS certificates and server ports.
+#[cfg(test)]
+pub(crate) fn init_test_config() -> &'static Config {
+    CONFIG.get_or_init(|| Config::read_env().expect("Invalid test configuration"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_penalties_are_parsed() {
+        assert_eq!(
+            parse_timeout_penalty("end_game", "10").unwrap(),
+            TimeoutPenalty::EndGame
+        );
+        assert_eq!(
+            parse_timeout_penalty("forfeit", "10").unwrap(),
+            TimeoutPenalty::Forfeit
+        );
+        assert_eq!(
+            parse_timeout_penalty("score_deduction", "7").unwrap(),
+            TimeoutPenalty::ScoreDeduction(7)
+        );
+    }
+
+    #[test]
+    fn default_clock_penalty_is_valid() {
+        assert_eq!(
+            parse_timeout_penalty(
+                DEFAULT_CLOCK_TIMEOUT_PENALTY,
+                &DEFAULT_CLOCK_PENALTY_POINTS.to_string()
+            )
+            .unwrap(),
+            TimeoutPenalty::EndGame
+        );
+    }
+
+    #[test]
+    fn invalid_clock_penalties_are_rejected() {
+        assert!(matches!(
+            parse_timeout_penalty("sudden_death", "10"),
+            Err(Error::Config(_))
+        ));
+        assert!(parse_timeout_penalty("score_deduction", "many").is_err());
+        assert!(parse_timeout_penalty("score_deduction", "-3").is_err());
+    }
+
+    #[test]
+    fn timeout_policies_are_parsed() {
+        assert_eq!(
+            parse_timeout_action("TIMEOUT_POLICY_BET", "auto_play").unwrap(),
+            TimeoutAction::AutoPlay
+        );
+        assert_eq!(
+            parse_timeout_action("TIMEOUT_POLICY_BET", "end_game").unwrap(),
+            TimeoutAction::EndGame
+        );
+        assert_eq!(
+            parse_timeout_action("TIMEOUT_POLICY_BET", DEFAULT_TIMEOUT_POLICY).unwrap(),
+            TimeoutAction::AutoPlay
+        );
+    }
+
+    #[test]
+    fn invalid_timeout_policies_name_their_variable() {
+        match parse_timeout_action("TIMEOUT_POLICY_HOKM", "skip") {
+            Err(Error::Config(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["Invalid timeout policy for TIMEOUT_POLICY_HOKM: skip".to_string()]
+                );
+            }
+            other => panic!("Expected a config error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn trusted_proxies_are_parsed() {
+        assert!(parse_trusted_proxies(DEFAULT_API_TRUSTED_PROXIES)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            parse_trusted_proxies("127.0.0.1, ::1").unwrap(),
+            vec![
+                "127.0.0.1".parse::<IpAddr>().unwrap(),
+                "::1".parse::<IpAddr>().unwrap()
+            ]
+        );
+        assert!(matches!(
+            parse_trusted_proxies("127.0.0.1,proxy.local"),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn timeout_policies_apply_per_decision() {
+        let policy: TimeoutPolicyConfig = TimeoutPolicyConfig {
+            team: TimeoutAction::AutoPlay,
+            bet: TimeoutAction::EndGame,
+            fold: TimeoutAction::AutoPlay,
+            hokm: TimeoutAction::EndGame,
+            play_card: TimeoutAction::AutoPlay,
+            bot_takeover_after: DEFAULT_TIMEOUT_BOT_TAKEOVER_AFTER,
+            end_game_after: DEFAULT_TIMEOUT_END_GAME_AFTER,
+        };
+        assert_eq!(policy.action_for("Bet"), &TimeoutAction::EndGame);
+        assert_eq!(policy.action_for("Hokm"), &TimeoutAction::EndGame);
+        assert_eq!(policy.action_for("Fold"), &TimeoutAction::AutoPlay);
+        assert_eq!(policy.action_for("PlayCard"), &TimeoutAction::AutoPlay);
+    }
+}
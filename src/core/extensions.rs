@@ -57,13 +57,36 @@ pub async fn send_message_to_player(
     }
 }
 
+pub fn choice_deadline() -> Option<tokio::time::Instant> {
+    let config: &crate::config::Config = crate::config::get_config();
+    config
+        .timeout
+        .player_choice_enabled
+        .then(|| tokio::time::Instant::now() + config.timeout.player_choice)
+}
+
+pub fn remaining_millis(deadline: tokio::time::Instant) -> u64 {
+    deadline
+        .saturating_duration_since(tokio::time::Instant::now())
+        .as_millis() as u64
+}
+
+pub fn deadline_timestamp(deadline: tokio::time::Instant) -> u64 {
+    let remaining: std::time::Duration =
+        deadline.saturating_duration_since(tokio::time::Instant::now());
+    (std::time::SystemTime::now() + remaining)
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 pub async fn timed_choice<T>(
     operation: impl std::future::Future<Output = Result<T>>,
+    deadline: Option<tokio::time::Instant>,
     player_name: String,
 ) -> Result<T> {
-    let config: &crate::config::Config = crate::config::get_config();
-    if config.timeout.player_choice_enabled {
-        return tokio::time::timeout(config.timeout.player_choice, operation)
+    if let Some(deadline) = deadline {
+        return tokio::time::timeout_at(deadline, operation)
             .await
             .timeout_context(format!("Player {player_name} took too long to make choice"));
     }
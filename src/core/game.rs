@@ -1,3 +1,4 @@
+use futures::future::Either;
 use tokio::{
     io::{AsyncWriteExt, ReadHalf, WriteHalf},
     sync::oneshot,
@@ -5,10 +6,17 @@ use tokio::{
     time::{timeout, Duration},
 };
 
-use super::{send_message_to_player, timed_choice};
+use super::{
+    choice_deadline, deadline_timestamp, remaining_millis, save_game_snapshot,
+    send_message_to_player, timed_choice, GameSnapshotRecord,
+};
 use crate::{
+    config::{TimeoutAction, TimeoutPenalty, TimeoutPolicyConfig},
     games::INVALID_RESPONSE,
-    models::{CorrelatedMessage, Player, PlayerConnection},
+    models::{
+        CorrelatedMessage, DisconnectInfo, GameCommand, GameSummary, PendingDemand, Player,
+        PlayerConnection, ReconnectionRequest, ReplayBuffer, TurnInfo,
+    },
     network::close_connection,
     prelude::*,
 };
@@ -19,25 +27,41 @@ pub trait Game: Send + Sync {
     fn clean_up(&mut self);
     fn generate_cards(&mut self) -> Result<()>;
     fn get_available_teams(&self) -> Result<Vec<(TeamId, String)>>;
+    fn get_capacity(&self) -> usize;
     fn get_field(&self) -> Vec<PlayerId>;
     fn get_id(&self) -> GameId;
     fn get_players(&mut self) -> Vec<&mut Player>;
     fn get_player_ids(&self) -> Vec<PlayerId>;
     fn get_player(&self, player_id: PlayerId) -> Result<&Player>;
+    fn get_player_mut(&mut self, player_id: PlayerId) -> Result<&mut Player>;
+    fn get_auto_choice(&self, player_id: PlayerId, message: &GameMessage) -> Result<PlayerChoice>;
     fn get_player_count(&self) -> usize;
+    fn get_pending_demand(&self) -> Option<&PendingDemand>;
     fn get_status(&self) -> &GameStatus;
     fn get_player_sender(&self, player_id: PlayerId) -> Result<&Sender<CorrelatedMessage>>;
-    fn get_player_receiver(
+    fn get_player_and_command_receivers(
         &mut self,
         player_id: PlayerId,
-    ) -> Result<&mut Receiver<Result<GameMessage>>>;
-    fn get_reconnection_receiver(&mut self) -> Result<&mut Receiver<(PlayerId, Stream)>>;
+    ) -> Result<(
+        &mut Receiver<Result<GameMessage>>,
+        &mut Receiver<GameCommand>,
+    )>;
+    fn get_command_receiver(&mut self) -> Result<&mut Receiver<GameCommand>>;
+    fn get_game_snapshot(&self) -> Result<Value>;
+    fn get_game_summary(&self) -> GameSummary;
+    fn get_game_type(&self) -> String;
+    fn get_persisted_state(&self) -> Result<Value>;
+    fn get_replay_buffer(&mut self) -> &mut ReplayBuffer;
+    fn get_time_banks(&mut self) -> &mut HashMap<PlayerId, Duration>;
     fn initialize_game(&mut self) -> Result<()>;
     fn is_full(&self) -> bool;
     fn remove_player_channels(&mut self, player_id: PlayerId);
     fn remove_player_connection(&mut self, player_id: PlayerId) -> Option<PlayerConnection>;
+    fn remove_player_seat(&mut self, player_id: PlayerId) -> Option<Player>;
+    fn restore_persisted_state(&mut self, state: Value) -> Result<()>;
+    fn set_pending_demand(&mut self, pending_demand: Option<PendingDemand>);
     fn set_status(&mut self, status: GameStatus);
-    fn setup_reconnection(&mut self) -> Result<Sender<(PlayerId, Stream)>>;
+    fn setup_commands(&mut self) -> Result<Sender<GameCommand>>;
     fn setup_player_connection(&mut self, player_id: PlayerId, connection: Stream) -> Result<()>;
     fn setup_receiver(
         &self,
@@ -47,16 +71,16 @@ pub trait Game: Send + Sync {
         req_sender: Sender<CorrelatedMessage>,
         shutdown_rx: oneshot::Receiver<()>,
     ) -> Result<JoinHandle<ReadHalf<Stream>>>;
-    async fn get_semi_state(&self) -> Result<Value>;
+    async fn apply_timeout_penalty(
+        &mut self,
+        player_id: PlayerId,
+        penalty: &TimeoutPenalty,
+    ) -> Result<()>;
     async fn run_game(&mut self) -> Result<()>;
     async fn setup_teams(&mut self) -> Result<()>;
     async fn send_player_full_state(&mut self, player_id: PlayerId) -> Result<()>;
     async fn update_shared_state(&self) -> Result<()>;
 
-    fn is_finished(&self) -> bool {
-        self.get_status() == &GameStatus::Finished
-    }
-
     fn is_started(&self) -> bool {
         self.get_status() == &GameStatus::Started
     }
@@ -69,8 +93,23 @@ pub trait Game: Send + Sync {
         let max_retries: usize = self.get_player_reconnection_max_retires();
         let mut attempt: usize = 0;
         loop {
-            let result: Option<Result<GameMessage>> =
-                self.get_player_receiver(player_id)?.recv().await;
+            let event: Either<Option<Result<GameMessage>>, GameCommand> = {
+                let (receiver, commands) = self.get_player_and_command_receivers(player_id)?;
+                tokio::select! {
+                    result = receiver.recv() => Either::Left(result),
+                    Some(command) = commands.recv() => Either::Right(command),
+                }
+            };
+            let result: Option<Result<GameMessage>> = match event {
+                Either::Left(result) => result,
+                Either::Right(command) => {
+                    self.handle_command(command).await?;
+                    if self.get_player(player_id)?.is_bot {
+                        return Ok(None);
+                    }
+                    continue;
+                }
+            };
             match result {
                 Some(Ok(message)) => {
                     return Ok(Some(message));
@@ -78,30 +117,19 @@ pub trait Game: Send + Sync {
                 Some(Err(_)) => {
                     attempt += 1;
                     if attempt > max_retries {
-                        return self
-                            .end_game(format!(
-                                "Player {player_name} failed after {attempt} attempts"
-                            ))
-                            .await
-                            .map(|_| None);
+                        println!("Player {player_name} failed after {attempt} attempts");
+                        return self.hand_seat_to_bot(player_id).await.map(|_| None);
                     }
                     tokio::time::sleep(Duration::from_millis(100 * (1 << attempt))).await;
                     let mut rec: Vec<(PlayerId, String)> = vec![(player_id, player_name.clone())];
-                    let _ = self.handle_player_reconnection(&mut rec).await;
-                    if !rec.is_empty() {
-                        return self
-                            .end_game(format!("Player {player_name} was disconnected"))
-                            .await
-                            .map(|_| None);
+                    self.handle_player_reconnection(&mut rec).await?;
+                    if self.get_player(player_id)?.is_bot {
+                        return Ok(None);
                     }
                 }
                 None => {
-                    return self
-                        .end_game(format!(
-                            "Player {player_name} disconnected (channel closed)"
-                        ))
-                        .await
-                        .map(|_| None);
+                    println!("Player {player_name} disconnected (channel closed)");
+                    return self.hand_seat_to_bot(player_id).await.map(|_| None);
                 }
             }
         }
@@ -124,16 +152,171 @@ pub trait Game: Send + Sync {
         get_config().timeout.player_reconnection_max_retries
     }
 
+    async fn announce_turn(
+        &mut self,
+        player_name: String,
+        action: String,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<()> {
+        self.broadcast_message(BroadcastMessage::PlayerTurn {
+            turn: TurnInfo {
+                name: player_name,
+                action,
+                deadline: deadline.map(deadline_timestamp),
+                remaining_time: deadline.map(remaining_millis),
+            },
+        })
+        .await
+    }
+
+    fn get_choice_deadline(&mut self, player_id: PlayerId) -> Option<tokio::time::Instant> {
+        let config: &Config = get_config();
+        if !config.clock.enabled {
+            return choice_deadline();
+        }
+        let time_bank: Duration = *self
+            .get_time_banks()
+            .entry(player_id)
+            .or_insert(config.clock.time_bank);
+        Some(tokio::time::Instant::now() + time_bank)
+    }
+
     async fn get_player_choice(
         &mut self,
         player_id: PlayerId,
         message: &mut GameMessage,
         passable: bool,
         max_value: usize,
+    ) -> Result<PlayerChoice> {
+        let config: &Config = get_config();
+        self.process_pending_commands().await?;
+        if self.get_player(player_id)?.is_bot {
+            return self.get_auto_choice(player_id, message);
+        }
+        let started_at: tokio::time::Instant = tokio::time::Instant::now();
+        let deadline: Option<tokio::time::Instant> = self.get_choice_deadline(player_id);
+        let result: Result<PlayerChoice> = self
+            .request_player_choice(player_id, message, passable, max_value, deadline)
+            .await;
+        match result {
+            Err(_)
+                if self
+                    .get_player(player_id)
+                    .is_ok_and(|player: &Player| player.is_bot) =>
+            {
+                self.get_auto_choice(player_id, message)
+            }
+            Err(Error::Timeout(reason)) => {
+                if config.clock.enabled {
+                    self.penalize_empty_time_bank(player_id, reason.clone())
+                        .await?;
+                }
+                self.record_player_timeout(player_id, message.message_type(), reason)
+                    .await?;
+                self.get_auto_choice(player_id, message)
+            }
+            result => {
+                if result.is_ok() {
+                    self.get_player_mut(player_id)?.consecutive_timeouts = 0;
+                }
+                self.charge_time_bank(player_id, started_at);
+                result
+            }
+        }
+    }
+
+    /// Takes the time spent since `started_at` off the player's time bank and adds the
+    /// increment. Does nothing unless clock mode is enabled.
+    fn charge_time_bank(&mut self, player_id: PlayerId, started_at: tokio::time::Instant) {
+        let config: &Config = get_config();
+        if !config.clock.enabled {
+            return;
+        }
+        let time_bank: &mut Duration = self
+            .get_time_banks()
+            .entry(player_id)
+            .or_insert(config.clock.time_bank);
+        *time_bank = time_bank.saturating_sub(started_at.elapsed()) + config.clock.increment;
+    }
+
+    /// Applies the clock penalty for a player whose time bank ran out. The bank is refilled
+    /// with the increment only; the move itself is left to the timeout policy.
+    async fn penalize_empty_time_bank(
+        &mut self,
+        player_id: PlayerId,
+        reason: String,
+    ) -> Result<()> {
+        let config: &Config = get_config();
+        if config.clock.penalty == TimeoutPenalty::EndGame {
+            self.end_game(reason.clone()).await?;
+            return Err(Error::Timeout(reason));
+        }
+        self.get_time_banks()
+            .insert(player_id, config.clock.increment);
+        let player_name: String = self.get_player(player_id)?.name.clone();
+        self.broadcast_message(BroadcastMessage::TimePenalty {
+            name: player_name,
+            penalty: config.clock.penalty.to_string(),
+        })
+        .await?;
+        self.apply_timeout_penalty(player_id, &config.clock.penalty)
+            .await
+    }
+
+    async fn record_player_timeout(
+        &mut self,
+        player_id: PlayerId,
+        decision: String,
+        reason: String,
+    ) -> Result<()> {
+        let policy: &TimeoutPolicyConfig = &get_config().timeout_policy;
+        let (player_name, timeouts, consecutive_timeouts) = {
+            let player: &mut Player = self.get_player_mut(player_id)?;
+            player.timeouts += 1;
+            player.consecutive_timeouts += 1;
+            (
+                player.name.clone(),
+                player.timeouts,
+                player.consecutive_timeouts,
+            )
+        };
+        println!("Player {player_name} timed out on {decision} ({timeouts} timeouts so far)");
+        if *policy.action_for(&decision) == TimeoutAction::EndGame
+            || (policy.end_game_after > 0 && timeouts >= policy.end_game_after)
+        {
+            self.end_game(reason.clone()).await?;
+            return Err(Error::Timeout(reason));
+        }
+        self.broadcast_message(BroadcastMessage::PlayerTimedOut {
+            name: player_name,
+            timeouts,
+        })
+        .await?;
+        if consecutive_timeouts >= policy.bot_takeover_after {
+            self.hand_seat_to_bot(player_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn request_player_choice(
+        &mut self,
+        player_id: PlayerId,
+        message: &mut GameMessage,
+        passable: bool,
+        max_value: usize,
+        deadline: Option<tokio::time::Instant>,
     ) -> Result<PlayerChoice> {
         let player_name: String = self.get_player(player_id)?.name.clone();
+        self.announce_turn(player_name.clone(), message.message_type(), deadline)
+            .await?;
         let operation = async {
             loop {
+                message.set_demand_remaining_time(deadline.map(remaining_millis));
+                self.set_pending_demand(Some(PendingDemand {
+                    player_id,
+                    message: message.clone(),
+                    deadline,
+                }));
                 self.send_message_to_player(player_id, player_name.clone(), message.clone())
                     .await?;
                 match self
@@ -184,11 +367,29 @@ pub trait Game: Send + Sync {
                 }
             }
         };
-        timed_choice(operation, player_name.clone()).await
+        let result: Result<PlayerChoice> =
+            timed_choice(operation, deadline, player_name.clone()).await;
+        self.set_pending_demand(None);
+        result
+    }
+
+    fn get_auto_team_choice(&self) -> Result<TeamId> {
+        self.get_available_teams()?
+            .first()
+            .map(|(team_id, _)| *team_id)
+            .ok_or(Error::Game("No team has a free seat".to_string()))
     }
 
     async fn get_player_team_choice(&mut self, player_id: PlayerId) -> Result<TeamId> {
+        self.process_pending_commands().await?;
+        if self.get_player(player_id)?.is_bot {
+            return self.get_auto_team_choice();
+        }
         let player_name: String = self.get_player(player_id)?.name.clone();
+        let started_at: tokio::time::Instant = tokio::time::Instant::now();
+        let deadline: Option<tokio::time::Instant> = self.get_choice_deadline(player_id);
+        self.announce_turn(player_name.clone(), "Team".to_string(), deadline)
+            .await?;
         let operation = async {
             loop {
                 let available_teams: Vec<(TeamId, String)> = self.get_available_teams()?;
@@ -199,6 +400,12 @@ pub trait Game: Send + Sync {
                         .collect(),
                     String::new(),
                 );
+                message.set_demand_remaining_time(deadline.map(remaining_millis));
+                self.set_pending_demand(Some(PendingDemand {
+                    player_id,
+                    message: message.clone(),
+                    deadline,
+                }));
                 self.send_message_to_player(player_id, player_name.clone(), message.clone())
                     .await?;
                 match self
@@ -226,7 +433,37 @@ pub trait Game: Send + Sync {
                 }
             }
         };
-        timed_choice(operation, player_name.clone()).await
+        let result: Result<TeamId> = timed_choice(operation, deadline, player_name.clone()).await;
+        self.set_pending_demand(None);
+        match result {
+            Err(_)
+                if self
+                    .get_player(player_id)
+                    .is_ok_and(|player: &Player| player.is_bot) =>
+            {
+                self.get_auto_team_choice()
+            }
+            Err(Error::Timeout(reason)) => {
+                // Without teams there is no score to deduct or game to forfeit yet, so an empty
+                // time bank only ends the game when that is the configured penalty.
+                self.charge_time_bank(player_id, started_at);
+                if get_config().clock.enabled
+                    && get_config().clock.penalty == TimeoutPenalty::EndGame
+                {
+                    self.end_game(reason.clone()).await?;
+                    return Err(Error::Timeout(reason));
+                }
+                self.record_player_timeout(player_id, "Team".to_string(), reason)
+                    .await?;
+                self.get_auto_team_choice()
+            }
+            Ok(team_id) => {
+                self.get_player_mut(player_id)?.consecutive_timeouts = 0;
+                self.charge_time_bank(player_id, started_at);
+                Ok(team_id)
+            }
+            result => result,
+        }
     }
 
     async fn send_message_to_player(
@@ -237,6 +474,17 @@ pub trait Game: Send + Sync {
     ) -> Result<()> {
         let max_retries: usize = self.get_player_reconnection_max_retires();
         let mut attempt: usize = 0;
+        if self.get_player_sender(player_id).is_err() && self.get_player(player_id)?.is_bot {
+            self.get_replay_buffer().record(player_id, message);
+            return Ok(());
+        }
+        let is_pending_demand: bool =
+            self.get_pending_demand()
+                .is_some_and(|pending: &PendingDemand| {
+                    pending.player_id == player_id && pending.message == message
+                });
+        let message: GameMessage = self.get_replay_buffer().record(player_id, message);
+        let seq: u64 = self.get_replay_buffer().last_seq(player_id);
         loop {
             let result: Result<()> = send_message_to_player(
                 self.get_player_sender(player_id)?,
@@ -249,21 +497,173 @@ pub trait Game: Send + Sync {
             }
             attempt += 1;
             if attempt > max_retries {
-                return self
-                    .end_game(format!(
-                        "Player {player_name} failed after {attempt} attempts"
-                    ))
-                    .await;
+                println!("Player {player_name} failed after {attempt} attempts");
+                return self.hand_seat_to_bot(player_id).await;
             }
             tokio::time::sleep(Duration::from_millis(100 * (1 << attempt))).await;
             let mut rec: Vec<(PlayerId, String)> = vec![(player_id, player_name.clone())];
-            let _ = self.handle_player_reconnection(&mut rec).await;
-            if !rec.is_empty() {
-                return self
-                    .end_game(format!("Player {player_name} was disconnected"))
-                    .await;
+            self.handle_player_reconnection(&mut rec).await?;
+            // A pending demand is re-issued on reconnection, and a resync replays or
+            // supersedes everything recorded before it, so sending again would duplicate it.
+            if is_pending_demand
+                || self.get_player(player_id)?.is_bot
+                || self.get_replay_buffer().was_resynced(player_id, seq)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn reissue_pending_demand(&mut self, player_id: PlayerId) -> Result<()> {
+        let pending_demand: Option<PendingDemand> = self
+            .get_pending_demand()
+            .filter(|pending: &&PendingDemand| pending.player_id == player_id)
+            .cloned();
+        let Some(PendingDemand {
+            mut message,
+            deadline,
+            ..
+        }) = pending_demand
+        else {
+            return Ok(());
+        };
+        message.set_demand_remaining_time(deadline.map(remaining_millis));
+        if let Some(deadline) = deadline {
+            println!(
+                "Re-issuing pending demand to player {player_id} with {:?} left",
+                deadline.saturating_duration_since(tokio::time::Instant::now())
+            );
+        }
+        let sender: Sender<CorrelatedMessage> = self.get_player_sender(player_id)?.clone();
+        let message: GameMessage = self.get_replay_buffer().record(player_id, message);
+        send_message_to_player(&sender, message, player_id).await
+    }
+
+    async fn resync_player(&mut self, player_id: PlayerId, last_seq: Option<u64>) -> Result<()> {
+        let missed_messages: Option<Vec<GameMessage>> = last_seq.and_then(|last_seq: u64| {
+            self.get_replay_buffer()
+                .missed_messages(player_id, last_seq)
+        });
+        let Some(missed_messages) = missed_messages else {
+            self.send_player_full_state(player_id).await?;
+            self.get_replay_buffer().mark_resynced(player_id);
+            return Ok(());
+        };
+        let sender: Sender<CorrelatedMessage> = self.get_player_sender(player_id)?.clone();
+        for message in missed_messages {
+            if let GameMessage::Sequenced { message: inner, .. } = &message {
+                if matches!(**inner, GameMessage::Demand { .. }) {
+                    continue;
+                }
             }
+            send_message_to_player(&sender, message, player_id).await?;
         }
+        self.get_replay_buffer().mark_resynced(player_id);
+        Ok(())
+    }
+
+    async fn end_previous_session(&mut self, player_id: PlayerId) {
+        println!("Player {player_id} is taking over their seat from another device");
+        if let Ok(sender) = self.get_player_sender(player_id) {
+            let sender: Sender<CorrelatedMessage> = sender.clone();
+            let _ = send_message_to_player(&sender, GameMessage::SessionMoved, player_id).await;
+        }
+    }
+
+    async fn accept_reconnection(&mut self, request: ReconnectionRequest) -> Result<bool> {
+        let ReconnectionRequest {
+            player_id,
+            connection: mut stream,
+            last_seq,
+            takeover,
+        } = request;
+        let Ok(player) = self.get_player(player_id) else {
+            let _ = close_connection(&mut stream).await;
+            return Ok(false);
+        };
+        if player.kicked {
+            println!("Rejecting reconnection of kicked player {player_id}");
+            let message: GameMessage = GameMessage::Kicked {
+                reason: "You were removed from this game".to_string(),
+            };
+            let _ = crate::network::send_message(&mut stream, &message).await;
+            let _ = close_connection(&mut stream).await;
+            return Ok(false);
+        }
+        if takeover {
+            self.end_previous_session(player_id).await;
+        }
+        if let Err(err) = self.reconnect_disconnected_player(player_id, stream).await {
+            eprintln!("Failed to reconnect player {player_id}: {err}");
+            return Ok(false);
+        }
+        if self.get_player(player_id)?.is_bot {
+            self.reclaim_seat(player_id).await?;
+        } else {
+            let player_name: String = self.get_player(player_id)?.name.clone();
+            let _ = self
+                ._broadcast_message(BroadcastMessage::PlayerReconnected { name: player_name })
+                .await;
+            self.resync_player(player_id, last_seq).await?;
+        }
+        self.reissue_pending_demand(player_id).await?;
+        Ok(true)
+    }
+
+    async fn handle_command(&mut self, command: GameCommand) -> Result<()> {
+        match command {
+            GameCommand::Reconnect(request) => self.accept_reconnection(*request).await.map(|_| ()),
+            GameCommand::Kick { player_id, reason } => {
+                println!(
+                    "Kicking player {player_id} from game {}: {reason}",
+                    self.get_id()
+                );
+                if let Ok(sender) = self.get_player_sender(player_id) {
+                    let sender: Sender<CorrelatedMessage> = sender.clone();
+                    let _ =
+                        send_message_to_player(&sender, GameMessage::Kicked { reason }, player_id)
+                            .await;
+                }
+                self.get_player_mut(player_id)?.kicked = true;
+                self.hand_seat_to_bot(player_id).await
+            }
+            GameCommand::Snapshot { respond_to } => {
+                let _ = respond_to.send(self.get_game_snapshot());
+                Ok(())
+            }
+            GameCommand::Summary { respond_to } => {
+                let _ = respond_to.send(self.get_game_summary());
+                Ok(())
+            }
+            GameCommand::Inspect { respond_to } => {
+                let _ = respond_to.send(self.get_persisted_state());
+                Ok(())
+            }
+            GameCommand::History {
+                player_id,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.get_replay_buffer().history(player_id));
+                Ok(())
+            }
+            GameCommand::Shutdown { reason } => self.end_game(reason).await,
+            GameCommand::Suspend { reason } => self.suspend_game(reason).await,
+            GameCommand::Notify { message } => self._broadcast_message(message).await.map(|_| ()),
+            GameCommand::Tell { player_id, message } => {
+                if let Ok(sender) = self.get_player_sender(player_id) {
+                    let sender: Sender<CorrelatedMessage> = sender.clone();
+                    let _ = send_message_to_player(&sender, message, player_id).await;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn process_pending_commands(&mut self) -> Result<()> {
+        while let Ok(command) = self.get_command_receiver()?.try_recv() {
+            self.handle_command(command).await?;
+        }
+        Ok(())
     }
 
     async fn handle_player_reconnection(
@@ -273,44 +673,37 @@ pub trait Game: Send + Sync {
         if players_to_reconnect.is_empty() {
             return Ok(());
         }
+        let deadline: tokio::time::Instant =
+            tokio::time::Instant::now() + self.get_player_reconnection_timeout();
+        for (_, player_name) in players_to_reconnect.iter() {
+            let _ = self
+                ._broadcast_message(BroadcastMessage::PlayerDisconnected {
+                    info: DisconnectInfo {
+                        name: player_name.clone(),
+                        deadline: deadline_timestamp(deadline),
+                        remaining_time: remaining_millis(deadline),
+                    },
+                })
+                .await;
+        }
         let reconnection_result: Result<Result<()>, tokio::time::error::Elapsed> =
-            tokio::time::timeout(self.get_player_reconnection_timeout(), async {
+            tokio::time::timeout_at(deadline, async {
                 while !players_to_reconnect.is_empty() {
-                    match self.get_reconnection_receiver()?.recv().await {
-                        Some((reconnecting_player_id, mut stream)) => {
-                            if let Some(pos) = players_to_reconnect
-                                .iter()
-                                .position(|(id, _)| *id == reconnecting_player_id)
-                            {
-                                match self
-                                    .reconnect_disconnected_player(reconnecting_player_id, stream)
-                                    .await
-                                {
-                                    Ok(_) => {
-                                        players_to_reconnect.remove(pos);
-                                        self.send_player_full_state(reconnecting_player_id).await?;
-                                    }
-                                    Err(e) => {
-                                        eprintln!(
-                                            "Failed to reconnect player {reconnecting_player_id}: {e}"
-                                        );
-                                    }
-                                }
-                            } else if self.get_player(reconnecting_player_id).is_ok() {
-                                match self.reconnect_disconnected_player(reconnecting_player_id, stream).await {
-                                    Ok(()) => {
-                                        self.send_player_full_state(reconnecting_player_id).await?;
-                                    }
-                                    Err(err) => {
-                                        eprintln!(
-                                            "Failed to reconnect existing player {reconnecting_player_id}: {err}"
-                                        );
-                                    }
-                                };
-                            } else {
-                                let _ = close_connection(&mut stream).await;
+                    match self.get_command_receiver()?.recv().await {
+                        Some(GameCommand::Reconnect(request)) => {
+                            let reconnecting_player_id: PlayerId = request.player_id;
+                            if self.accept_reconnection(*request).await? {
+                                players_to_reconnect
+                                    .retain(|(id, _)| *id != reconnecting_player_id);
                             }
                         }
+                        Some(command) => {
+                            self.handle_command(command).await?;
+                            players_to_reconnect.retain(|(id, _)| {
+                                self.get_player(*id)
+                                    .is_ok_and(|player: &Player| !player.is_bot)
+                            });
+                        }
                         None => break,
                     }
                 }
@@ -321,20 +714,58 @@ pub trait Game: Send + Sync {
             Ok(Ok(())) => Ok(()),
             Ok(Err(e)) => Err(e),
             Err(_) => {
-                let remaining_players: Vec<String> = players_to_reconnect
-                    .iter()
-                    .map(|(_, name)| name.clone())
-                    .collect();
-                self.end_game(format!(
-                    "Players [{}] failed to reconnect within {:?}",
-                    remaining_players.join(", "),
-                    self.get_player_reconnection_timeout()
-                ))
-                .await
+                let timeout: Duration = self.get_player_reconnection_timeout();
+                for (player_id, player_name) in std::mem::take(players_to_reconnect) {
+                    println!("Player {player_name} failed to reconnect within {timeout:?}");
+                    self.hand_seat_to_bot(player_id).await?;
+                }
+                Ok(())
             }
         }
     }
 
+    async fn hand_seat_to_bot(&mut self, player_id: PlayerId) -> Result<()> {
+        let _ = self.close_player_connection(player_id).await;
+        let player_name: String = {
+            let player: &mut Player = self.get_player_mut(player_id)?;
+            player.is_bot = true;
+            player.replaced_by_bot = true;
+            player.name.clone()
+        };
+        println!("Handing the seat of player {player_name} to a bot");
+        let _ = self
+            ._broadcast_message(BroadcastMessage::BotTakeover { name: player_name })
+            .await;
+        self.end_game_if_all_bots().await
+    }
+
+    async fn reclaim_seat(&mut self, player_id: PlayerId) -> Result<()> {
+        let player_name: String = {
+            let player: &mut Player = self.get_player_mut(player_id)?;
+            player.is_bot = false;
+            player.consecutive_timeouts = 0;
+            player.name.clone()
+        };
+        println!("Player {player_name} reclaimed their seat from a bot");
+        let _ = self
+            ._broadcast_message(BroadcastMessage::SeatReclaimed { name: player_name })
+            .await;
+        self.resync_player(player_id, None).await
+    }
+
+    async fn end_game_if_all_bots(&mut self) -> Result<()> {
+        let all_bots: bool = self.get_player_ids().into_iter().all(|id: PlayerId| {
+            self.get_player(id)
+                .is_ok_and(|player: &Player| player.is_bot)
+        });
+        if all_bots {
+            return self
+                .end_game("Every seat was taken over by a bot".to_string())
+                .await;
+        }
+        Ok(())
+    }
+
     fn setup_sender(
         &self,
         writer: WriteHalf<Stream>,
@@ -376,20 +807,21 @@ pub trait Game: Send + Sync {
             .iter()
             .map(|player: &&mut Player| (player.id, player.name.clone()))
             .collect();
-        let player_info: Vec<(PlayerId, String, Sender<CorrelatedMessage>)> = infos
+        let player_info: Vec<(PlayerId, String, Sender<CorrelatedMessage>, GameMessage)> = infos
             .into_iter()
             .filter_map(|(player_id, player_name)| {
-                if let Ok(sender) = self.get_player_sender(player_id) {
-                    return Some((player_id, player_name, sender.clone()));
-                }
-                None
+                let sender: Sender<CorrelatedMessage> =
+                    self.get_player_sender(player_id).ok()?.clone();
+                let game_message: GameMessage = self
+                    .get_replay_buffer()
+                    .record(player_id, game_message.clone());
+                Some((player_id, player_name, sender, game_message))
             })
             .collect();
         let send_futures: Vec<_> = player_info
             .into_iter()
-            .map(|(player_id, player_name, sender)| {
-                let game_message: GameMessage = game_message.clone();
-                async move {
+            .map(
+                |(player_id, player_name, sender, game_message)| async move {
                     if send_message_to_player(&sender, game_message, player_id)
                         .await
                         .is_err()
@@ -397,8 +829,8 @@ pub trait Game: Send + Sync {
                         return Some((player_id, player_name));
                     }
                     None
-                }
-            })
+                },
+            )
             .collect();
         let results: Vec<Option<(PlayerId, String)>> =
             futures::future::join_all(send_futures).await;
@@ -407,42 +839,14 @@ pub trait Game: Send + Sync {
     }
 
     async fn broadcast_message(&mut self, message: BroadcastMessage) -> Result<()> {
-        let max_retries: usize = self.get_player_reconnection_max_retires();
-        let mut attempt: usize = 0;
-        loop {
-            let mut failed_players: Vec<(PlayerId, String)> =
-                self._broadcast_message(message.clone()).await?;
-            if failed_players.is_empty() {
-                return Ok(());
-            }
-            attempt += 1;
-            if attempt > max_retries {
-                return self
-                    .end_game(format!(
-                        "Players [{}] failed to reconnect after {} attempts",
-                        failed_players
-                            .iter()
-                            .map(|(_, name)| name.clone())
-                            .join(", "),
-                        attempt
-                    ))
-                    .await;
-            }
-            let _ = self.handle_player_reconnection(&mut failed_players).await;
-            if failed_players.is_empty() {
-                continue;
-            } else {
-                return self
-                    .end_game(format!(
-                        "Players [{}] failed to reconnect within 1 minute",
-                        failed_players
-                            .iter()
-                            .map(|(_, name)| name.clone())
-                            .join(", ")
-                    ))
-                    .await;
-            }
+        let mut failed_players: Vec<(PlayerId, String)> = self._broadcast_message(message).await?;
+        if failed_players.is_empty() {
+            return Ok(());
         }
+        // Reconnected players are resynced from the replay buffer, which already holds this
+        // broadcast, so there is nothing left to resend here. Players who don't make it back
+        // in time are handed to a bot.
+        self.handle_player_reconnection(&mut failed_players).await
     }
 
     async fn close_player_connection(&mut self, player_id: PlayerId) -> Result<()> {
@@ -467,7 +871,59 @@ pub trait Game: Send + Sync {
         Ok(())
     }
 
+    async fn remove_player(&mut self, player_id: PlayerId) -> Result<()> {
+        self.get_player(player_id)?;
+        let _ = self.close_player_connection(player_id).await;
+        if let Some(player) = self.remove_player_seat(player_id) {
+            println!("Removed player {} from game {}", player.name, self.get_id());
+            let _ = self
+                ._broadcast_message(BroadcastMessage::PlayerLeft { name: player.name })
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn save_snapshot(&self) {
+        let game_id: GameId = self.get_id();
+        let result: Result<()> = async {
+            let record: GameSnapshotRecord = GameSnapshotRecord {
+                game_id,
+                game_type: self.get_game_type(),
+                saved_at: chrono::Utc::now(),
+                state: self.get_persisted_state()?,
+            };
+            save_game_snapshot(&record).await
+        }
+        .await;
+        if let Err(err) = result {
+            eprintln!("Failed to save snapshot of game {game_id}: {err}");
+        }
+    }
+
+    async fn resume_game(&mut self) -> Result<()> {
+        let mut players_to_reconnect: Vec<(PlayerId, String)> = self
+            .get_player_ids()
+            .into_iter()
+            .filter_map(|player_id: PlayerId| {
+                let player: &Player = self.get_player(player_id).ok()?;
+                (!player.is_bot).then(|| (player_id, player.name.clone()))
+            })
+            .collect();
+        println!(
+            "Waiting for {} players to reconnect to restored game {}",
+            players_to_reconnect.len(),
+            self.get_id()
+        );
+        self.handle_player_reconnection(&mut players_to_reconnect)
+            .await?;
+        self.end_game_if_all_bots().await?;
+        self.run_game().await
+    }
+
     async fn start_game(&mut self) -> Result<()> {
+        if self.is_started() {
+            return self.resume_game().await;
+        }
         self.setup_teams().await?;
         self.broadcast_message(BroadcastMessage::GameStarting)
             .await?;
@@ -485,4 +941,21 @@ pub trait Game: Send + Sync {
         self.set_status(GameStatus::Ended);
         Err(Error::Other("Game ended".to_string()))
     }
+
+    /// Games are only snapshotted once their first round starts, so a game still choosing
+    /// teams has nothing to resume from and is ended instead.
+    async fn suspend_game(&mut self, reason: String) -> Result<()> {
+        if !self.is_started() {
+            return self.end_game(reason).await;
+        }
+        let _ = self
+            ._broadcast_message(BroadcastMessage::GameSuspended { reason })
+            .await;
+        for player_id in self.get_player_ids() {
+            let _ = self.close_player_connection(player_id).await;
+        }
+        self.clean_up();
+        self.set_status(GameStatus::Suspended);
+        Err(Error::Other("Game suspended".to_string()))
+    }
 }
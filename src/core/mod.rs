@@ -1,9 +1,13 @@
 pub mod extensions;
 pub mod game;
 pub mod registry;
+pub mod snapshot;
+pub mod supervisor;
 pub mod types;
 
 pub use extensions::*;
 pub use game::*;
 pub use registry::*;
+pub use snapshot::*;
+pub use supervisor::*;
 pub use types::*;
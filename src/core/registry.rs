@@ -1,15 +1,26 @@
 #![allow(dead_code)]
 
+use dashmap::{mapref::one::Ref, DashMap};
 use std::{
-    collections::HashMap,
-    sync::{Arc, OnceLock},
-    time::SystemTime,
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, SystemTime},
 };
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::sync::{oneshot, Mutex, MutexGuard, RwLock};
 
 use crate::{
+    core::{
+        deadline_timestamp, send_message_to_player,
+        snapshot::{load_game_snapshots, remove_game_snapshot, GameSnapshotRecord},
+        supervisor::{GameFailure, GameOutcome, GameSupervisor},
+    },
     games::*,
-    models::{UserSession, UserSessionStatus},
+    models::{
+        GameCommand, GameSummary, QueueStatus, ReconnectionRequest, UserSession, UserSessionStatus,
+    },
     network::{close_connection, send_message},
     prelude::*,
 };
@@ -24,66 +35,146 @@ pub struct GameQueue {
 pub struct ActiveGame {
     pub id: GameId,
     pub game_type: String,
-    pub game: Arc<Mutex<BoxGame>>,
     pub created_at: SystemTime,
     pub started_at: SystemTime,
     pub timeout_at: Option<SystemTime>,
-    pub reconnection_sender: Sender<(PlayerId, Stream)>,
+    pub command_sender: Sender<GameCommand>,
     pub player_ids: Vec<PlayerId>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+pub struct ActiveGameInfo {
+    pub id: GameId,
+    pub game_type: String,
+    pub created_at: SystemTime,
+    pub started_at: SystemTime,
+    pub player_ids: Vec<PlayerId>,
+}
+
+#[derive(Clone, Debug)]
+pub struct QueueInfo {
+    pub game_type: String,
+    pub game_id: GameId,
+    pub created_at: SystemTime,
+    pub player_ids: Vec<PlayerId>,
+    pub status: Option<QueueStatus>,
+}
+
+impl ActiveGame {
+    fn info(&self) -> ActiveGameInfo {
+        ActiveGameInfo {
+            id: self.id,
+            game_type: self.game_type.clone(),
+            created_at: self.created_at,
+            started_at: self.started_at,
+            player_ids: self.player_ids.clone(),
+        }
+    }
+
+    fn is_timed_out(&self, now: SystemTime) -> bool {
+        self.timeout_at
+            .map(|timeout_at: SystemTime| now >= timeout_at)
+            .unwrap_or(false)
+    }
+}
+
+const FILL_RATE_SAMPLES: usize = 20;
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SUSPEND_GRACE_PERIOD: Duration = Duration::from_secs(10);
+const GAME_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Registry state is split so that unrelated work never contends on one lock: sessions
+/// live in a concurrent map, while queues and running games are sharded by game type.
+#[derive(Clone, Default)]
 pub struct GameRegistry {
     factories: Arc<HashMap<String, GameFactory>>,
-    state: Arc<Mutex<RegistryState>>,
+    shards: Arc<HashMap<String, GameTypeShard>>,
+    user_sessions: Arc<DashMap<UserId, UserSession>>,
+    supervisor: GameSupervisor,
+    draining: Arc<AtomicBool>,
 }
 
 #[derive(Default)]
-struct RegistryState {
-    active_games: HashMap<GameId, ActiveGame>,
-    game_queues: HashMap<String, GameQueue>,
-    user_sessions: HashMap<UserId, UserSession>,
+struct GameTypeShard {
+    queue: Mutex<Option<GameQueue>>,
+    active_games: RwLock<HashMap<GameId, ActiveGame>>,
+    seat_fill_times: Mutex<VecDeque<Duration>>,
 }
 
-impl Default for GameRegistry {
-    fn default() -> Self {
-        Self {
-            factories: Arc::new(HashMap::new()),
-            state: Arc::new(Mutex::new(RegistryState {
-                active_games: HashMap::new(),
-                game_queues: HashMap::new(),
-                user_sessions: HashMap::new(),
-            })),
+impl GameTypeShard {
+    async fn record_fill_time(&self, queue: &GameQueue, seats: usize) {
+        let elapsed: Duration = queue.created_at.elapsed().unwrap_or_default();
+        let mut samples: MutexGuard<VecDeque<Duration>> = self.seat_fill_times.lock().await;
+        samples.push_back(elapsed / seats.max(1) as u32);
+        while samples.len() > FILL_RATE_SAMPLES {
+            samples.pop_front();
         }
     }
+
+    async fn average_seat_fill_time(&self) -> Option<Duration> {
+        let samples: MutexGuard<VecDeque<Duration>> = self.seat_fill_times.lock().await;
+        if samples.is_empty() {
+            return None;
+        }
+        Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+    }
 }
 
 impl GameRegistry {
     pub fn new() -> Self {
-        let mut factories: HashMap<String, GameFactory> = HashMap::new();
-        factories.insert("Qafoon".to_string(), Qafoon::boxed_new);
-        let registry: GameRegistry = Self {
-            factories: Arc::new(factories),
-            state: Arc::new(Mutex::new(RegistryState::default())),
-        };
+        let registry: GameRegistry = Self::with_factories(Self::default_factories());
         registry.start_cleanup_service();
         registry
     }
 
+    /// Builds a registry without the background cleanup service, so callers such as the
+    /// benchmark harness can drive it in isolation.
+    pub fn with_factories(factories: HashMap<String, GameFactory>) -> Self {
+        let shards: HashMap<String, GameTypeShard> = factories
+            .keys()
+            .map(|game_type: &String| (game_type.clone(), GameTypeShard::default()))
+            .collect();
+        Self {
+            factories: Arc::new(factories),
+            shards: Arc::new(shards),
+            user_sessions: Arc::new(DashMap::new()),
+            supervisor: GameSupervisor::default(),
+            draining: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn default_factories() -> HashMap<String, GameFactory> {
+        let mut factories: HashMap<String, GameFactory> = HashMap::new();
+        factories.insert("Qafoon".to_string(), Qafoon::boxed_new);
+        factories
+    }
+
+    fn get_shard(&self, game_type: &str) -> Result<&GameTypeShard> {
+        self.shards
+            .get(game_type)
+            .ok_or_else(|| Error::Registry(format!("Game {game_type} is not supported")))
+    }
+
     pub fn get_available_games(&self) -> Vec<String> {
         self.factories.keys().cloned().collect()
     }
 
     pub async fn is_user_in_game(&self, user_id: UserId) -> bool {
-        self.state.lock().await.user_sessions.contains_key(&user_id)
+        self.user_sessions.contains_key(&user_id)
     }
 
     pub async fn get_user_session(&self, user_id: UserId) -> Option<UserSession> {
-        self.state.lock().await.user_sessions.get(&user_id).cloned()
+        self.user_sessions
+            .get(&user_id)
+            .map(|session: Ref<UserId, UserSession>| session.clone())
+    }
+
+    pub async fn register_user_session(&self, session: UserSession) {
+        self.user_sessions.insert(session.user_id, session);
     }
 
     async fn remove_user_session(&self, user_id: UserId) {
-        self.state.lock().await.user_sessions.remove(&user_id);
+        self.user_sessions.remove(&user_id);
     }
 
     pub async fn add_player_to_queue(
@@ -91,102 +182,201 @@ impl GameRegistry {
         user_id: UserId,
         username: String,
         game_choice: String,
+        jti: uuid::Uuid,
         mut connection: Stream,
     ) -> Result<()> {
-        {
-            let state: MutexGuard<RegistryState> = self.state.lock().await;
-            if let Some(existing_session) = state.user_sessions.get(&user_id) {
-                let message: GameMessage = match existing_session.status {
-                    UserSessionStatus::InQueue => GameMessage::AlreadyInQueueError {
-                        game_type: existing_session.game_type.clone(),
-                    },
-                    UserSessionStatus::InGame => GameMessage::AlreadyInGameError {
-                        game_type: existing_session.game_type.clone(),
-                    },
-                };
-                let _ = send_message(&mut connection, &message).await;
-                let _ = close_connection(&mut connection).await;
-                return Err(Error::Registry("User already in game".to_string()));
+        let existing_session: Option<UserSession> = self.get_user_session(user_id).await;
+        if let Some(existing_session) = existing_session {
+            if existing_session.status == UserSessionStatus::InGame {
+                println!("User {username} is claiming their seat from a new device");
+                return self
+                    .take_over_seat(user_id, existing_session.game_id, jti, connection)
+                    .await;
             }
+            let message: GameMessage = GameMessage::AlreadyInQueueError {
+                game_type: existing_session.game_type,
+            };
+            let _ = send_message(&mut connection, &message).await;
+            let _ = close_connection(&mut connection).await;
+            return Err(Error::Registry("User already in queue".to_string()));
         }
-        let game_arc: Arc<Mutex<BoxGame>> = self.get_or_create_queue(&game_choice).await?;
-        let (game_id, player_added) = {
-            let mut game: MutexGuard<BoxGame> = game_arc.lock().await;
-            if game.get_player_count() == 0 {
-                if let Err(err) = game.initialize_game() {
-                    self.cleanup_failed_queue(&game_choice).await;
-                    return Err(err);
-                }
-            }
-            let game_id: GameId = game.get_id();
-            let reconnection_token: crate::auth::TokenPair =
-                match crate::auth::generate_reconnection_token(user_id, game_id) {
-                    Ok(token) => token,
-                    Err(e) => {
-                        if game.get_player_count() == 0 {
-                            drop(game);
-                            self.cleanup_failed_queue(&game_choice).await;
-                        }
-                        return Err(Error::Registry(format!(
-                            "Failed to generate reconnection token: {e}"
-                        )));
-                    }
-                };
-            if let Err(e) = send_message(
-                &mut connection,
-                &GameMessage::ReconnectionToken {
-                    token: reconnection_token.access_token,
-                },
-            )
+        // The shard queue stays locked until the player is seated and a full game promoted,
+        // so no other joiner can pick a queue that is about to become a running game.
+        let shard: &GameTypeShard = self.get_shard(&game_choice)?;
+        let mut queue: MutexGuard<Option<GameQueue>> = shard.queue.lock().await;
+        // Checked under the queue lock, so a drain either sees this player in the queue it
+        // closes or the player sees the drain.
+        if let Err(err) = self.check_accepting_players() {
+            drop(queue);
+            let _ = close_connection(&mut connection).await;
+            return Err(err);
+        }
+        let game_arc: Arc<Mutex<BoxGame>> =
+            self.get_or_create_queue(&mut queue, &game_choice).await?;
+        let mut game: MutexGuard<BoxGame> = game_arc.lock().await;
+        let game_id: GameId = match self
+            .seat_player(&mut game, user_id, username.clone(), jti, connection)
             .await
-            {
+        {
+            Ok(game_id) => game_id,
+            Err(err) => {
                 if game.get_player_count() == 0 {
-                    drop(game);
-                    self.cleanup_failed_queue(&game_choice).await;
+                    *queue = None;
                 }
-                return Err(Error::Registry(format!(
-                    "Failed to send reconnection token: {e}"
-                )));
+                return Err(err);
             }
-            match game.add_player(user_id, username.clone(), connection) {
-                Ok(_) => Ok((game_id, game.is_full())),
-                Err(err) => {
-                    if game.get_player_count() == 0 {
-                        drop(game);
-                        self.cleanup_failed_queue(&game_choice).await;
-                    }
-                    Err(err)
-                }
-            }
-        }?;
-        {
-            self.state.lock().await.user_sessions.insert(
-                user_id,
-                UserSession {
-                    user_id,
-                    username: username.clone(),
-                    game_id,
-                    game_type: game_choice.clone(),
-                    status: UserSessionStatus::InQueue,
-                    joined_at: SystemTime::now(),
-                },
-            );
-        }
+        };
+        self.register_user_session(UserSession {
+            user_id,
+            username: username.clone(),
+            game_id,
+            game_type: game_choice.clone(),
+            status: UserSessionStatus::InQueue,
+            joined_at: SystemTime::now(),
+        })
+        .await;
         println!("Added user {username} to queue for {game_choice}");
-        if player_added {
-            self.promote_full_game(&game_choice, game_arc).await?;
+        if game.is_full() {
+            return self
+                .promote_full_game(shard, &mut queue, &mut game, &game_choice)
+                .await;
         }
+        drop(game);
+        drop(queue);
+        self.broadcast_queue_status(&game_choice).await;
         Ok(())
     }
 
+    async fn seat_player(
+        &self,
+        game: &mut BoxGame,
+        user_id: UserId,
+        username: String,
+        jti: uuid::Uuid,
+        mut connection: Stream,
+    ) -> Result<GameId> {
+        if game.get_player_count() == 0 {
+            game.initialize_game()?;
+        }
+        let game_id: GameId = game.get_id();
+        let reconnection_token: crate::auth::TokenPair =
+            crate::auth::generate_reconnection_token(user_id, game_id, jti).map_err(|e| {
+                Error::Registry(format!("Failed to generate reconnection token: {e}"))
+            })?;
+        send_message(
+            &mut connection,
+            &GameMessage::ReconnectionToken {
+                token: reconnection_token.access_token,
+            },
+        )
+        .await
+        .map_err(|e| Error::Registry(format!("Failed to send reconnection token: {e}")))?;
+        game.add_player(user_id, username, connection)?;
+        Ok(game_id)
+    }
+
+    pub async fn get_queue_status(&self, game_type: &str) -> Option<QueueStatus> {
+        let shard: &GameTypeShard = self.get_shard(game_type).ok()?;
+        let (game_arc, created_at) = {
+            let queue: MutexGuard<Option<GameQueue>> = shard.queue.lock().await;
+            let queue: &GameQueue = queue.as_ref()?;
+            (queue.game.clone(), queue.created_at)
+        };
+        let seat_fill_time: Option<Duration> = shard.average_seat_fill_time().await;
+        let game: MutexGuard<BoxGame> = game_arc.lock().await;
+        let players_present: usize = game.get_player_count();
+        let seats_needed: usize = game.get_capacity().saturating_sub(players_present);
+        let cutoff_in: Duration = get_config()
+            .timeout
+            .queue_cutoff
+            .saturating_sub(created_at.elapsed().unwrap_or_default());
+        Some(QueueStatus {
+            players_present,
+            seats_needed,
+            cutoff_in: cutoff_in.as_secs(),
+            estimated_wait: seat_fill_time
+                .map(|seat_fill_time: Duration| (seat_fill_time * seats_needed as u32).as_secs()),
+        })
+    }
+
+    async fn broadcast_queue_status(&self, game_type: &str) {
+        let Some(status) = self.get_queue_status(game_type).await else {
+            return;
+        };
+        let Ok(shard) = self.get_shard(game_type) else {
+            return;
+        };
+        let game_arc: Option<Arc<Mutex<BoxGame>>> = shard
+            .queue
+            .lock()
+            .await
+            .as_ref()
+            .map(|queue: &GameQueue| queue.game.clone());
+        if let Some(game_arc) = game_arc {
+            let _ = game_arc
+                .lock()
+                .await
+                ._broadcast_message(BroadcastMessage::QueueStatus { status })
+                .await;
+        }
+    }
+
     pub async fn reconnect_player(
         &self,
         player_id: PlayerId,
         game_id: GameId,
         connection: Stream,
+        last_seq: Option<u64>,
+    ) -> Result<()> {
+        self.send_reconnection_request(
+            game_id,
+            ReconnectionRequest {
+                player_id,
+                connection,
+                last_seq,
+                takeover: false,
+            },
+        )
+        .await
+    }
+
+    async fn take_over_seat(
+        &self,
+        user_id: UserId,
+        game_id: GameId,
+        jti: uuid::Uuid,
+        mut connection: Stream,
+    ) -> Result<()> {
+        let reconnection_token: crate::auth::TokenPair =
+            crate::auth::generate_reconnection_token(user_id, game_id, jti).map_err(|e| {
+                Error::Registry(format!("Failed to generate reconnection token: {e}"))
+            })?;
+        send_message(
+            &mut connection,
+            &GameMessage::ReconnectionToken {
+                token: reconnection_token.access_token,
+            },
+        )
+        .await?;
+        self.send_reconnection_request(
+            game_id,
+            ReconnectionRequest {
+                player_id: user_id,
+                connection,
+                last_seq: None,
+                takeover: true,
+            },
+        )
+        .await
+    }
+
+    async fn send_reconnection_request(
+        &self,
+        game_id: GameId,
+        request: ReconnectionRequest,
     ) -> Result<()> {
         if let Some(sender) = self.get_active_game_sender(game_id).await {
-            if let Err(err) = sender.send((player_id, connection)).await {
+            if let Err(err) = sender.send(GameCommand::Reconnect(Box::new(request))).await {
                 return Err(Error::Registry(format!(
                     "Failed to reconnect player: {err}"
                 )));
@@ -195,23 +385,103 @@ impl GameRegistry {
         Ok(())
     }
 
-    async fn get_or_create_queue(&self, game_choice: &str) -> Result<Arc<Mutex<BoxGame>>> {
-        let mut state: MutexGuard<RegistryState> = self.state.lock().await;
-        if let Some(existing_queue) = state.game_queues.get(game_choice) {
-            if existing_queue.is_waiting {
-                if let Ok(game) = existing_queue.game.try_lock() {
-                    if !game.is_full() {
-                        return Ok(existing_queue.game.clone());
-                    }
-                }
+    async fn send_command(&self, game_id: GameId, command: GameCommand) -> Result<()> {
+        let sender: Sender<GameCommand> = self
+            .get_active_game_sender(game_id)
+            .await
+            .ok_or_else(|| Error::Registry(format!("Game {game_id} is not running")))?;
+        sender
+            .send(command)
+            .await
+            .map_err(|err| Error::Registry(format!("Failed to reach game {game_id}: {err}")))
+    }
+
+    /// Sends a command that carries a reply channel and waits for the game to answer it
+    /// between moves.
+    async fn request_from_game<Response>(
+        &self,
+        game_id: GameId,
+        command: impl FnOnce(oneshot::Sender<Response>) -> GameCommand,
+    ) -> Result<Response> {
+        let (respond_to, response) = oneshot::channel();
+        self.send_command(game_id, command(respond_to)).await?;
+        tokio::time::timeout(GAME_REQUEST_TIMEOUT, response)
+            .await
+            .map_err(|_| Error::Timeout(format!("Game {game_id} did not answer in time")))?
+            .map_err(|_| Error::Registry(format!("Game {game_id} stopped before answering")))
+    }
+
+    pub async fn get_game_snapshot(&self, game_id: GameId) -> Result<Value> {
+        self.request_from_game(game_id, |respond_to| GameCommand::Snapshot { respond_to })
+            .await?
+    }
+
+    pub async fn get_game_summary(&self, game_id: GameId) -> Result<GameSummary> {
+        self.request_from_game(game_id, |respond_to| GameCommand::Summary { respond_to })
+            .await
+    }
+
+    /// Returns the full persisted state of a game, including every player's hand.
+    pub async fn inspect_game(&self, game_id: GameId) -> Result<Value> {
+        self.request_from_game(game_id, |respond_to| GameCommand::Inspect { respond_to })
+            .await?
+    }
+
+    /// Returns the messages the game recently sent to a player, as kept for resyncing them.
+    pub async fn get_player_history(
+        &self,
+        game_id: GameId,
+        player_id: PlayerId,
+    ) -> Result<Vec<GameMessage>> {
+        self.request_from_game(game_id, |respond_to| GameCommand::History {
+            player_id,
+            respond_to,
+        })
+        .await
+    }
+
+    pub async fn kick_player(
+        &self,
+        game_id: GameId,
+        player_id: PlayerId,
+        reason: String,
+    ) -> Result<()> {
+        self.send_command(game_id, GameCommand::Kick { player_id, reason })
+            .await
+    }
+
+    /// Sends a message to one player of a running game without otherwise touching their seat.
+    pub async fn tell_player(
+        &self,
+        game_id: GameId,
+        player_id: PlayerId,
+        message: GameMessage,
+    ) -> Result<()> {
+        self.send_command(game_id, GameCommand::Tell { player_id, message })
+            .await
+    }
+
+    pub async fn shutdown_game(&self, game_id: GameId, reason: String) -> Result<()> {
+        self.send_command(game_id, GameCommand::Shutdown { reason })
+            .await
+    }
+
+    async fn get_or_create_queue(
+        &self,
+        queue: &mut Option<GameQueue>,
+        game_choice: &str,
+    ) -> Result<Arc<Mutex<BoxGame>>> {
+        if let Some(existing_queue) = queue.as_ref() {
+            if existing_queue.is_waiting && !existing_queue.game.lock().await.is_full() {
+                return Ok(existing_queue.game.clone());
             }
         }
-        self.create_new_queue_locked(&mut state, game_choice).await
+        self.create_new_queue_locked(queue, game_choice).await
     }
 
     async fn create_new_queue_locked(
         &self,
-        state: &mut RegistryState,
+        queue: &mut Option<GameQueue>,
         game_choice: &str,
     ) -> Result<Arc<Mutex<BoxGame>>> {
         let factory: &GameFactory = self
@@ -225,298 +495,583 @@ impl GameRegistry {
             created_at: SystemTime::now(),
             is_waiting: true,
         };
-        state.game_queues.insert(game_choice.to_string(), new_queue);
+        *queue = Some(new_queue);
         Ok(game)
     }
 
+    /// Moves a full queued game into the running games. Callers hold the shard queue lock
+    /// and the game lock, so the queue is gone before any other joiner can look at it.
     async fn promote_full_game(
         &self,
+        shard: &GameTypeShard,
+        queue: &mut Option<GameQueue>,
+        queued_game: &mut BoxGame,
         game_choice: &str,
-        game_arc: Arc<Mutex<BoxGame>>,
     ) -> Result<()> {
-        let (game_id, reconnection_sender, player_ids) = {
-            let mut game: MutexGuard<BoxGame> = game_arc.lock().await;
-            let player_ids: Vec<PlayerId> = game.get_player_ids();
-            (game.get_id(), game.setup_reconnection()?, player_ids)
-        };
-        {
-            let mut state: MutexGuard<RegistryState> = self.state.lock().await;
-            for player_id in &player_ids {
-                if let Some(session) = state.user_sessions.get_mut(player_id) {
-                    session.status = UserSessionStatus::InGame;
-                }
-            }
-        }
-        let registry_clone: GameRegistry = self.clone();
-        let game_arc_clone: Arc<Mutex<BoxGame>> = game_arc.clone();
-        {
-            let mut state: MutexGuard<RegistryState> = self.state.lock().await;
-            if let Some(queue) = state.game_queues.remove(game_choice) {
-                let timeout_at: SystemTime = SystemTime::now() + get_config().timeout.game_duration;
-                let active_game: ActiveGame = ActiveGame {
-                    id: game_id,
-                    game_type: game_choice.to_string(),
-                    game: game_arc.clone(),
-                    created_at: queue.created_at,
-                    started_at: SystemTime::now(),
-                    timeout_at: Some(timeout_at),
-                    reconnection_sender,
-                    player_ids: player_ids.clone(),
-                };
-                state.active_games.insert(game_id, active_game);
-            } else {
-                return Err(Error::Registry(format!(
-                    "Queue for {game_choice} was removed before promotion"
-                )));
+        let factory: &GameFactory = self
+            .factories
+            .get(game_choice)
+            .ok_or_else(|| Error::Registry(format!("Game {game_choice} is not supported")))?;
+        let queue: GameQueue = queue.take().ok_or_else(|| {
+            Error::Registry(format!(
+                "Queue for {game_choice} was removed before promotion"
+            ))
+        })?;
+        // The running game is owned by its own task from here on. Anyone still holding the
+        // old queue handle only sees an empty placeholder.
+        let mut game: BoxGame = std::mem::replace(queued_game, factory());
+        let player_ids: Vec<PlayerId> = game.get_player_ids();
+        let command_sender: Sender<GameCommand> = game.setup_commands()?;
+        let game_id: GameId = game.get_id();
+        for player_id in &player_ids {
+            if let Some(mut session) = self.user_sessions.get_mut(player_id) {
+                session.status = UserSessionStatus::InGame;
             }
         }
+        shard.record_fill_time(&queue, player_ids.len()).await;
+        let timeout_at: SystemTime = SystemTime::now() + get_config().timeout.game_duration;
+        let active_game: ActiveGame = ActiveGame {
+            id: game_id,
+            game_type: game_choice.to_string(),
+            created_at: queue.created_at,
+            started_at: SystemTime::now(),
+            timeout_at: Some(timeout_at),
+            command_sender,
+            player_ids: player_ids.clone(),
+        };
+        shard
+            .active_games
+            .write()
+            .await
+            .insert(game_id, active_game);
+        self.clone()
+            .spawn_game(game, game_id, game_choice.to_string(), player_ids);
+        Ok(())
+    }
+
+    fn spawn_game(
+        self,
+        game: BoxGame,
+        game_id: GameId,
+        game_type: String,
+        player_ids: Vec<PlayerId>,
+    ) {
         tokio::spawn(async move {
-            let result: Result<()> = Self::run_full_game_with_timeout(
-                game_arc_clone,
-                game_id,
-                registry_clone,
-                player_ids,
-            )
-            .await;
+            let result: Result<()> = self
+                .run_supervised_game(game, game_id, game_type, player_ids)
+                .await;
             if let Err(err) = result {
                 eprintln!("Game {game_id} failed: {err}");
             }
         });
+    }
+
+    pub async fn restore_saved_games(&self) {
+        let records: Vec<GameSnapshotRecord> = match load_game_snapshots().await {
+            Ok(records) => records,
+            Err(err) => {
+                eprintln!("Failed to load game snapshots: {err}");
+                return;
+            }
+        };
+        for record in records {
+            let game_id: GameId = record.game_id;
+            if let Err(err) = self.restore_game(record).await {
+                eprintln!("Failed to restore game {game_id}: {err}");
+                let _ = remove_game_snapshot(game_id).await;
+            }
+        }
+    }
+
+    async fn restore_game(&self, record: GameSnapshotRecord) -> Result<()> {
+        let factory: &GameFactory = self.factories.get(&record.game_type).ok_or_else(|| {
+            Error::Registry(format!("Game {} is not supported", record.game_type))
+        })?;
+        let mut game: BoxGame = factory();
+        game.restore_persisted_state(record.state)?;
+        let game_id: GameId = game.get_id();
+        let player_ids: Vec<PlayerId> = game.get_player_ids();
+        let command_sender: Sender<GameCommand> = game.setup_commands()?;
+        for player_id in &player_ids {
+            self.register_user_session(UserSession {
+                user_id: *player_id,
+                username: game.get_player(*player_id)?.name.clone(),
+                game_id,
+                game_type: record.game_type.clone(),
+                status: UserSessionStatus::InGame,
+                joined_at: SystemTime::now(),
+            })
+            .await;
+        }
+        let now: SystemTime = SystemTime::now();
+        let active_game: ActiveGame = ActiveGame {
+            id: game_id,
+            game_type: record.game_type.clone(),
+            created_at: now,
+            started_at: now,
+            timeout_at: Some(now + get_config().timeout.game_duration),
+            command_sender,
+            player_ids: player_ids.clone(),
+        };
+        self.get_shard(&record.game_type)?
+            .active_games
+            .write()
+            .await
+            .insert(game_id, active_game);
+        println!(
+            "Restored game {game_id} from the snapshot saved at {}",
+            record.saved_at
+        );
+        self.clone()
+            .spawn_game(game, game_id, record.game_type, player_ids);
         Ok(())
     }
 
-    async fn run_full_game_with_timeout(
-        game_arc: Arc<Mutex<BoxGame>>,
+    async fn run_supervised_game(
+        &self,
+        mut game: BoxGame,
         game_id: GameId,
-        registry: GameRegistry,
+        game_type: String,
         player_ids: Vec<PlayerId>,
     ) -> Result<()> {
-        let game_result: Result<Result<()>, tokio::time::error::Elapsed> =
-            tokio::time::timeout(get_config().timeout.game_duration, async {
-                game_arc.lock().await.start_game().await
-            })
+        let outcome: GameOutcome = self
+            .supervisor
+            .run(&mut game, &game_type, get_config().timeout.game_duration)
             .await;
-        match game_result {
-            Ok(Ok(_)) => {
+        match outcome {
+            GameOutcome::Completed => {
                 println!("Game {game_id} completed successfully");
             }
-            Ok(Err(err)) => {
-                eprintln!("Game {game_id} failed: {err}");
-                if let Ok(mut game) = game_arc.try_lock() {
-                    let _ = game
-                        .broadcast_message(BroadcastMessage::GameError {
-                            error: err.to_string(),
-                        })
-                        .await;
-                }
+            GameOutcome::Ended => {
+                println!("Game {game_id} was ended early");
             }
-            Err(_) => {
+            GameOutcome::TimedOut => {
                 eprintln!("Game {game_id} timed out");
-                if let Ok(mut game) = game_arc.try_lock() {
-                    let _ = game.broadcast_message(BroadcastMessage::GameTimeout).await;
-                    for player_id in &player_ids {
-                        let _ = game.close_player_connection(*player_id).await;
-                    }
+                let _ = game._broadcast_message(BroadcastMessage::GameTimeout).await;
+                for player_id in &player_ids {
+                    let _ = game.close_player_connection(*player_id).await;
                 }
             }
+            GameOutcome::Suspended => {
+                println!("Game {game_id} was suspended and can be resumed from its snapshot");
+            }
+            GameOutcome::Failed(_) => {}
+        }
+        if !matches!(outcome, GameOutcome::Suspended) {
+            if let Err(err) = remove_game_snapshot(game_id).await {
+                eprintln!("Failed to remove snapshot of game {game_id}: {err}");
+            }
         }
         for player_id in player_ids {
-            registry.remove_user_session(player_id).await;
+            self.remove_user_session(player_id).await;
         }
-        registry.remove_game(game_id).await?;
+        self.remove_game(game_id).await?;
         Ok(())
     }
 
-    async fn cleanup_failed_queue(&self, game_choice: &str) {
-        let (should_remove, user_ids_to_cleanup) = {
-            let state: MutexGuard<RegistryState> = self.state.lock().await;
-            if let Some(queue) = state.game_queues.get(game_choice) {
-                if let Ok(game) = queue.game.try_lock() {
-                    let player_count: usize = game.get_player_count();
-                    let user_ids: Vec<PlayerId> = if player_count == 0 {
-                        game.get_player_ids()
-                    } else {
-                        vec![]
-                    };
-                    (player_count == 0, user_ids)
-                } else {
-                    (false, vec![])
-                }
-            } else {
-                (false, vec![])
-            }
-        };
-        if should_remove {
-            let mut state: MutexGuard<RegistryState> = self.state.lock().await;
-            state.game_queues.remove(game_choice);
-            for user_id in user_ids_to_cleanup {
-                state.user_sessions.remove(&user_id);
-            }
-        }
+    pub fn get_crashed_games_count(&self) -> usize {
+        self.supervisor.get_crashed_games_count()
+    }
+
+    pub fn get_recent_game_failures(&self) -> Vec<GameFailure> {
+        self.supervisor.get_recent_failures()
     }
 
     fn start_cleanup_service(&self) {
-        let state_arc: Arc<Mutex<RegistryState>> = self.state.clone();
+        let registry: GameRegistry = self.clone();
         let config: &Config = get_config();
         tokio::spawn(async move {
             let mut interval: tokio::time::Interval =
                 tokio::time::interval(config.game_server.queue_clean_up_interval);
             loop {
                 interval.tick().await;
-                if let Err(err) = Self::cleanup_tick(&state_arc, config).await {
+                if let Err(err) = registry.cleanup_tick(config).await {
                     eprintln!("Cleanup service error: {err}");
                 }
             }
         });
     }
 
-    async fn cleanup_tick(
-        state_arc: &Arc<Mutex<RegistryState>>,
-        config: &Config,
-    ) -> Result<()> {
+    async fn cleanup_tick(&self, config: &Config) -> Result<()> {
         let now: SystemTime = SystemTime::now();
         let queue_cutoff: SystemTime = now - config.timeout.queue_cutoff;
-        let (expired_queues, finished_games, initial_game_count) = {
-            let state: MutexGuard<RegistryState> = state_arc.lock().await;
-            let expired_queues: Vec<(String, Arc<Mutex<BoxGame>>)> = state
-                .game_queues
-                .iter()
-                .filter(|(_, queue)| queue.created_at <= queue_cutoff)
-                .map(|(game_type, queue)| (game_type.clone(), queue.game.clone()))
-                .collect();
-            let finished_games: Vec<(GameId, Arc<Mutex<BoxGame>>, Vec<PlayerId>)> = state
-                .active_games
-                .iter()
-                .filter_map(|(game_id, active_game)| {
-                    let should_remove_timeout: bool = active_game
-                        .timeout_at
-                        .map(|timeout_at: SystemTime| now >= timeout_at)
-                        .unwrap_or(false);
-                    let should_remove_finished: bool = active_game
-                        .game
-                        .try_lock()
-                        .map(|game: MutexGuard<BoxGame>| game.is_finished())
-                        .unwrap_or(false);
-                    if should_remove_timeout || should_remove_finished {
-                        Some((
-                            *game_id,
-                            active_game.game.clone(),
-                            active_game.player_ids.clone(),
-                        ))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            let initial_game_count: usize = state.active_games.len();
-            (expired_queues, finished_games, initial_game_count)
-        };
-        {
-            let mut state: MutexGuard<RegistryState> = state_arc.lock().await;
-            for (game_type, _) in &expired_queues {
-                if let Some(queue) = state.game_queues.remove(game_type) {
-                    if let Ok(game) = queue.game.try_lock() {
-                        for player_id in game.get_player_ids() {
-                            state.user_sessions.remove(&player_id);
-                        }
+        let mut cleaned_games: usize = 0;
+        for (game_type, shard) in self.shards.iter() {
+            let expired_queue: Option<GameQueue> = {
+                let mut queue: MutexGuard<Option<GameQueue>> = shard.queue.lock().await;
+                match queue.as_ref() {
+                    Some(existing_queue) if existing_queue.created_at <= queue_cutoff => {
+                        queue.take()
                     }
+                    _ => None,
                 }
-                println!("Cleaned up expired queue for {game_type}");
-            }
-            for (game_id, _, player_ids) in &finished_games {
-                state.active_games.remove(game_id);
-                for player_id in player_ids {
-                    state.user_sessions.remove(player_id);
-                }
-                println!("Cleaned up finished game {game_id}");
-            }
-        }
-        for (_, game_arc) in expired_queues {
-            if let Ok(mut game) = game_arc.try_lock() {
-                let _ = game.broadcast_message(BroadcastMessage::QueueTimeout).await;
+            };
+            if let Some(expired_queue) = expired_queue {
+                let mut game: MutexGuard<BoxGame> = expired_queue.game.lock().await;
+                let _ = game
+                    ._broadcast_message(BroadcastMessage::QueueTimeout)
+                    .await;
                 let player_ids: Vec<PlayerId> = game.get_player_ids();
-                for player_id in player_ids {
-                    let _ = game.close_player_connection(player_id).await;
+                for player_id in &player_ids {
+                    let _ = game.close_player_connection(*player_id).await;
                 }
+                drop(game);
+                for player_id in &player_ids {
+                    self.user_sessions.remove(player_id);
+                }
+                println!("Cleaned up expired queue for {game_type}");
             }
-        }
-        for (_, game_arc, _) in finished_games {
-            if let Ok(mut game) = game_arc.try_lock() {
-                let player_ids: Vec<PlayerId> = game.get_player_ids();
-                for player_id in player_ids {
-                    let _ = game.close_player_connection(player_id).await;
+            let finished_games: Vec<ActiveGame> = {
+                let mut active_games: tokio::sync::RwLockWriteGuard<HashMap<GameId, ActiveGame>> =
+                    shard.active_games.write().await;
+                let finished_ids: Vec<GameId> = active_games
+                    .values()
+                    .filter(|active_game: &&ActiveGame| {
+                        active_game.is_timed_out(now) || active_game.command_sender.is_closed()
+                    })
+                    .map(|active_game: &ActiveGame| active_game.id)
+                    .collect();
+                finished_ids
+                    .iter()
+                    .filter_map(|game_id: &GameId| active_games.remove(game_id))
+                    .collect()
+            };
+            for active_game in finished_games {
+                for player_id in &active_game.player_ids {
+                    self.user_sessions.remove(player_id);
+                }
+                if active_game.is_timed_out(now) {
+                    let _ = active_game
+                        .command_sender
+                        .send(GameCommand::Shutdown {
+                            reason: format!(
+                                "Game {} exceeded its maximum duration",
+                                active_game.id
+                            ),
+                        })
+                        .await;
                 }
+                println!("Cleaned up finished game {}", active_game.id);
+                cleaned_games += 1;
             }
         }
-        let final_count: usize = state_arc.lock().await.active_games.len();
-        let cleaned_games: usize = initial_game_count.saturating_sub(final_count);
         if cleaned_games > 0 {
             println!("Cleaned up {cleaned_games} finished games");
         }
         Ok(())
     }
 
-    pub async fn get_active_game(&self, game_id: GameId) -> Option<Arc<Mutex<BoxGame>>> {
-        self.state
-            .lock()
-            .await
-            .active_games
-            .get(&game_id)
-            .map(|active: &ActiveGame| active.game.clone())
-    }
-
-    pub async fn get_active_game_sender(
-        &self,
-        game_id: GameId,
-    ) -> Option<Sender<(PlayerId, Stream)>> {
-        self.state
-            .lock()
-            .await
-            .active_games
-            .get(&game_id)
-            .map(|active: &ActiveGame| active.reconnection_sender.clone())
+    pub async fn get_active_game_sender(&self, game_id: GameId) -> Option<Sender<GameCommand>> {
+        for shard in self.shards.values() {
+            if let Some(active_game) = shard.active_games.read().await.get(&game_id) {
+                return Some(active_game.command_sender.clone());
+            }
+        }
+        None
     }
 
     pub async fn remove_game(&self, game_id: GameId) -> Result<()> {
-        if let Some(active_game) = self.state.lock().await.active_games.remove(&game_id) {
-            drop(active_game.reconnection_sender);
-            if let Ok(mut game) = active_game.game.try_lock() {
-                for player_id in game.get_player_ids() {
-                    let _ = game.close_player_connection(player_id).await;
-                }
+        for shard in self.shards.values() {
+            let active_game: Option<ActiveGame> = shard.active_games.write().await.remove(&game_id);
+            if let Some(active_game) = active_game {
+                let _ = active_game.command_sender.try_send(GameCommand::Shutdown {
+                    reason: format!("Game {game_id} was removed"),
+                });
+                println!("Cleaned up game {game_id} with all its resources");
+                break;
             }
-            println!("Cleaned up game {game_id} with all its resources");
         }
         Ok(())
     }
 
+    async fn get_active_game_senders(&self) -> Vec<Sender<GameCommand>> {
+        let mut senders: Vec<Sender<GameCommand>> = Vec::new();
+        for shard in self.shards.values() {
+            senders.extend(
+                shard
+                    .active_games
+                    .read()
+                    .await
+                    .values()
+                    .map(|active_game: &ActiveGame| active_game.command_sender.clone()),
+            );
+        }
+        senders
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    fn check_accepting_players(&self) -> Result<()> {
+        if self.is_draining() {
+            return Err(Error::Registry(
+                "Server is shutting down, no new players are accepted".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stops new queue joins, tells every waiting and playing user about the maintenance
+    /// and gives active games until the deadline to finish. Games still running after that
+    /// are suspended, which keeps their last snapshot for the next start; games still in
+    /// team selection have no snapshot yet and are ended.
+    pub async fn drain(&self, drain_timeout: Duration) {
+        self.draining.store(true, Ordering::Relaxed);
+        let deadline: tokio::time::Instant = tokio::time::Instant::now() + drain_timeout;
+        let notice: BroadcastMessage = BroadcastMessage::ServerMaintenance {
+            deadline: deadline_timestamp(deadline),
+        };
+        for (game_type, shard) in self.shards.iter() {
+            let queue: Option<GameQueue> = shard.queue.lock().await.take();
+            let Some(queue) = queue else {
+                continue;
+            };
+            let mut game: MutexGuard<BoxGame> = queue.game.lock().await;
+            let _ = game._broadcast_message(notice.clone()).await;
+            let player_ids: Vec<PlayerId> = game.get_player_ids();
+            for player_id in &player_ids {
+                let _ = game.close_player_connection(*player_id).await;
+            }
+            drop(game);
+            for player_id in &player_ids {
+                self.user_sessions.remove(player_id);
+            }
+            println!("Closed the {game_type} queue for maintenance");
+        }
+        for sender in self.get_active_game_senders().await {
+            let _ = sender
+                .send(GameCommand::Notify {
+                    message: notice.clone(),
+                })
+                .await;
+        }
+        println!(
+            "Waiting up to {drain_timeout:?} for {} active games to finish",
+            self.get_active_games_count().await
+        );
+        self.wait_for_active_games(deadline).await;
+        let remaining: Vec<Sender<GameCommand>> = self.get_active_game_senders().await;
+        if remaining.is_empty() {
+            return;
+        }
+        println!(
+            "Suspending {} games that did not finish before the drain deadline",
+            remaining.len()
+        );
+        for sender in remaining {
+            let _ = sender
+                .send(GameCommand::Suspend {
+                    reason: "The server is restarting for maintenance".to_string(),
+                })
+                .await;
+        }
+        self.wait_for_active_games(tokio::time::Instant::now() + SUSPEND_GRACE_PERIOD)
+            .await;
+    }
+
+    async fn wait_for_active_games(&self, deadline: tokio::time::Instant) {
+        while self.get_active_games_count().await > 0 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+
     pub async fn list_active_games(&self) -> Vec<(GameId, String)> {
-        self.state
-            .lock()
-            .await
-            .active_games
-            .values()
-            .map(|game: &ActiveGame| (game.id, game.game_type.clone()))
+        let mut games: Vec<(GameId, String)> = Vec::new();
+        for shard in self.shards.values() {
+            games.extend(
+                shard
+                    .active_games
+                    .read()
+                    .await
+                    .values()
+                    .map(|game: &ActiveGame| (game.id, game.game_type.clone())),
+            );
+        }
+        games
+    }
+
+    pub async fn list_active_game_infos(&self) -> Vec<ActiveGameInfo> {
+        let mut games: Vec<ActiveGameInfo> = Vec::new();
+        for shard in self.shards.values() {
+            games.extend(
+                shard
+                    .active_games
+                    .read()
+                    .await
+                    .values()
+                    .map(ActiveGame::info),
+            );
+        }
+        games.sort_by_key(|game: &ActiveGameInfo| game.started_at);
+        games
+    }
+
+    pub async fn get_active_game_info(&self, game_id: GameId) -> Option<ActiveGameInfo> {
+        for shard in self.shards.values() {
+            if let Some(active_game) = shard.active_games.read().await.get(&game_id) {
+                return Some(active_game.info());
+            }
+        }
+        None
+    }
+
+    pub async fn list_queues(&self) -> Vec<QueueInfo> {
+        let mut queues: Vec<QueueInfo> = Vec::new();
+        for (game_type, shard) in self.shards.iter() {
+            let queue: Option<(Arc<Mutex<BoxGame>>, SystemTime)> = shard
+                .queue
+                .lock()
+                .await
+                .as_ref()
+                .map(|queue: &GameQueue| (queue.game.clone(), queue.created_at));
+            let Some((game_arc, created_at)) = queue else {
+                continue;
+            };
+            let (game_id, player_ids) = {
+                let game: MutexGuard<BoxGame> = game_arc.lock().await;
+                (game.get_id(), game.get_player_ids())
+            };
+            queues.push(QueueInfo {
+                game_type: game_type.clone(),
+                game_id,
+                created_at,
+                player_ids,
+                status: self.get_queue_status(game_type).await,
+            });
+        }
+        queues
+    }
+
+    pub async fn list_user_sessions(&self) -> Vec<UserSession> {
+        self.user_sessions
+            .iter()
+            .map(
+                |session: dashmap::mapref::multiple::RefMulti<UserId, UserSession>| {
+                    session.value().clone()
+                },
+            )
             .collect()
     }
 
+    /// Drops sessions that point at neither a running game nor the current queue of their
+    /// game type, which is what keeps a user stuck on "already in a game" after a failure.
+    pub async fn clear_stale_sessions(&self) -> usize {
+        let scan_started_at: SystemTime = SystemTime::now();
+        let mut live_game_ids: Vec<GameId> = Vec::new();
+        for shard in self.shards.values() {
+            live_game_ids.extend(shard.active_games.read().await.keys().copied());
+            let queue_game: Option<Arc<Mutex<BoxGame>>> = shard
+                .queue
+                .lock()
+                .await
+                .as_ref()
+                .map(|queue: &GameQueue| queue.game.clone());
+            if let Some(game_arc) = queue_game {
+                live_game_ids.push(game_arc.lock().await.get_id());
+            }
+        }
+        let before: usize = self.user_sessions.len();
+        self.user_sessions.retain(|_, session: &mut UserSession| {
+            session.joined_at >= scan_started_at || live_game_ids.contains(&session.game_id)
+        });
+        let cleared: usize = before.saturating_sub(self.user_sessions.len());
+        if cleared > 0 {
+            println!("Cleared {cleared} stale user sessions");
+        }
+        cleared
+    }
+
     pub async fn get_games_by_type(&self, game_type: &str) -> Vec<GameId> {
-        self.state
+        let Ok(shard) = self.get_shard(game_type) else {
+            return vec![];
+        };
+        shard.active_games.read().await.keys().copied().collect()
+    }
+
+    pub async fn get_active_games_count(&self) -> usize {
+        let mut count: usize = 0;
+        for shard in self.shards.values() {
+            count += shard.active_games.read().await.len();
+        }
+        count
+    }
+
+    pub async fn remove_player_from_queue(&self, user_id: UserId) -> Result<()> {
+        let session: UserSession = self
+            .get_user_session(user_id)
+            .await
+            .ok_or_else(|| Error::Registry(format!("User {user_id} has no session")))?;
+        if session.status != UserSessionStatus::InQueue {
+            return Err(Error::Registry(format!("User {user_id} is not in a queue")));
+        }
+        let shard: &GameTypeShard = self.get_shard(&session.game_type)?;
+        let game_arc: Option<Arc<Mutex<BoxGame>>> = shard
+            .queue
             .lock()
             .await
-            .active_games
-            .values()
-            .filter(|game: &&ActiveGame| game.game_type == game_type)
-            .map(|game: &ActiveGame| game.id)
-            .collect()
+            .as_ref()
+            .map(|queue: &GameQueue| queue.game.clone());
+        let is_empty: bool = match &game_arc {
+            Some(game_arc) => {
+                let mut game: MutexGuard<BoxGame> = game_arc.lock().await;
+                if game.get_id() == session.game_id {
+                    game.remove_player(user_id).await?;
+                }
+                game.get_player_count() == 0
+            }
+            None => false,
+        };
+        self.user_sessions.remove(&user_id);
+        if is_empty {
+            let mut queue: MutexGuard<Option<GameQueue>> = shard.queue.lock().await;
+            if queue
+                .as_ref()
+                .zip(game_arc.as_ref())
+                .is_some_and(|(queue, game_arc)| Arc::ptr_eq(&queue.game, game_arc))
+            {
+                *queue = None;
+            }
+            println!("Dropped empty {} queue", session.game_type);
+        }
+        println!("Removed user {user_id} from {} queue", session.game_type);
+        if !is_empty {
+            self.broadcast_queue_status(&session.game_type).await;
+        }
+        Ok(())
     }
 
-    pub async fn get_active_games_count(&self) -> usize {
-        self.state.lock().await.active_games.len()
+    /// Takes a user out of the queue they are waiting in, telling them why first. Users who
+    /// are not queued are left alone.
+    pub async fn kick_from_queue(&self, user_id: UserId, reason: String) -> Result<bool> {
+        let Some(session) = self.get_user_session(user_id).await else {
+            return Ok(false);
+        };
+        if session.status != UserSessionStatus::InQueue {
+            return Ok(false);
+        }
+        let shard: &GameTypeShard = self.get_shard(&session.game_type)?;
+        let game_arc: Option<Arc<Mutex<BoxGame>>> = shard
+            .queue
+            .lock()
+            .await
+            .as_ref()
+            .map(|queue: &GameQueue| queue.game.clone());
+        if let Some(game_arc) = game_arc {
+            let game: MutexGuard<BoxGame> = game_arc.lock().await;
+            if game.get_id() == session.game_id {
+                if let Ok(sender) = game.get_player_sender(user_id) {
+                    let _ = send_message_to_player(sender, GameMessage::Kicked { reason }, user_id)
+                        .await;
+                }
+            }
+        }
+        self.remove_player_from_queue(user_id).await?;
+        Ok(true)
     }
 
     pub async fn force_remove_user_session(&self, user_id: UserId) -> Result<()> {
-        if let Some(session) = self.state.lock().await.user_sessions.remove(&user_id) {
+        if let Some((_, session)) = self.user_sessions.remove(&user_id) {
             println!(
                 "Force removed user {user_id} from {} session in {}",
                 session.status.as_str(),
@@ -533,10 +1088,122 @@ pub fn get_game_registry() -> &'static GameRegistry {
     GAME_REGISTRY.get_or_init(GameRegistry::new)
 }
 
-pub async fn get_active_game(game_id: GameId) -> Option<Arc<Mutex<BoxGame>>> {
-    get_game_registry().get_active_game(game_id).await
+pub async fn get_game_snapshot(game_id: GameId) -> Result<Value> {
+    get_game_registry().get_game_snapshot(game_id).await
 }
 
 pub async fn list_all_active_games() -> Vec<(GameId, String)> {
     get_game_registry().list_active_games().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::init_test_config;
+
+    async fn open_queue(registry: &GameRegistry, game_type: &str) -> GameId {
+        let shard: &GameTypeShard = registry.get_shard(game_type).unwrap();
+        let mut queue: MutexGuard<Option<GameQueue>> = shard.queue.lock().await;
+        let game: Arc<Mutex<BoxGame>> = registry
+            .get_or_create_queue(&mut queue, game_type)
+            .await
+            .unwrap();
+        let game_id: GameId = game.lock().await.get_id();
+        game_id
+    }
+
+    fn session(game_id: GameId, joined_at: SystemTime) -> UserSession {
+        UserSession {
+            user_id: UserId::new_v4(),
+            username: "player".to_string(),
+            game_id,
+            game_type: "Qafoon".to_string(),
+            status: UserSessionStatus::InQueue,
+            joined_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn queue_waits_are_estimated_from_recent_seat_fill_times() {
+        init_test_config();
+        let registry: GameRegistry =
+            GameRegistry::with_factories(GameRegistry::default_factories());
+        assert!(registry.get_queue_status("Qafoon").await.is_none());
+        open_queue(&registry, "Qafoon").await;
+        let status: QueueStatus = registry.get_queue_status("Qafoon").await.unwrap();
+        assert_eq!((status.players_present, status.seats_needed), (0, 4));
+        assert_eq!(status.estimated_wait, None);
+        assert!(status.cutoff_in <= get_config().timeout.queue_cutoff.as_secs());
+
+        let shard: &GameTypeShard = registry.get_shard("Qafoon").unwrap();
+        shard
+            .seat_fill_times
+            .lock()
+            .await
+            .extend([Duration::from_secs(2), Duration::from_secs(4)]);
+        let status: QueueStatus = registry.get_queue_status("Qafoon").await.unwrap();
+        assert_eq!(status.estimated_wait, Some(12));
+    }
+
+    #[tokio::test]
+    async fn seat_fill_times_only_keep_recent_samples() {
+        let shard: GameTypeShard = GameTypeShard::default();
+        let queue: GameQueue = GameQueue {
+            game_type: "Qafoon".to_string(),
+            game: Arc::new(Mutex::new(Qafoon::boxed_new())),
+            created_at: SystemTime::now() - Duration::from_secs(40),
+            is_waiting: true,
+        };
+        for _ in 0..FILL_RATE_SAMPLES + 5 {
+            shard.record_fill_time(&queue, 4).await;
+        }
+        assert_eq!(shard.seat_fill_times.lock().await.len(), FILL_RATE_SAMPLES);
+        let average: Duration = shard.average_seat_fill_time().await.unwrap();
+        assert!(average >= Duration::from_secs(10) && average < Duration::from_secs(11));
+    }
+
+    #[tokio::test]
+    async fn stale_sessions_are_cleared_and_queued_ones_kept() {
+        init_test_config();
+        let registry: GameRegistry =
+            GameRegistry::with_factories(GameRegistry::default_factories());
+        let game_id: GameId = open_queue(&registry, "Qafoon").await;
+        let an_hour_ago: SystemTime = SystemTime::now() - Duration::from_secs(3600);
+        let queued: UserSession = session(game_id, an_hour_ago);
+        let stale: UserSession = session(GameId::new_v4(), an_hour_ago);
+        registry.register_user_session(queued.clone()).await;
+        registry.register_user_session(stale.clone()).await;
+
+        let queues: Vec<QueueInfo> = registry.list_queues().await;
+        assert_eq!(queues.len(), 1);
+        assert_eq!(queues[0].game_id, game_id);
+        assert!(queues[0].status.is_some());
+
+        assert_eq!(registry.clear_stale_sessions().await, 1);
+        assert!(registry.is_user_in_game(queued.user_id).await);
+        assert!(!registry.is_user_in_game(stale.user_id).await);
+        assert_eq!(registry.list_user_sessions().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn draining_closes_the_queues_and_refuses_new_players() {
+        let registry: GameRegistry =
+            GameRegistry::with_factories(GameRegistry::default_factories());
+        open_queue(&registry, "Qafoon").await;
+        assert!(registry.check_accepting_players().is_ok());
+
+        registry.drain(Duration::ZERO).await;
+        assert!(registry.is_draining());
+        assert!(matches!(
+            registry.check_accepting_players(),
+            Err(Error::Registry(_))
+        ));
+        assert!(registry
+            .get_shard("Qafoon")
+            .unwrap()
+            .queue
+            .lock()
+            .await
+            .is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+use crate::prelude::*;
+
+const SNAPSHOT_EXTENSION: &str = "json";
+
+/// A persisted copy of an in-progress game, written at safe points so the game can be
+/// restored after the server restarts.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct GameSnapshotRecord {
+    pub game_id: GameId,
+    pub game_type: String,
+    pub saved_at: DateTime<Utc>,
+    pub state: Value,
+}
+
+fn get_snapshot_file(directory: &Path, game_id: GameId) -> PathBuf {
+    directory.join(format!("{game_id}.{SNAPSHOT_EXTENSION}"))
+}
+
+pub async fn save_game_snapshot(record: &GameSnapshotRecord) -> Result<()> {
+    let config: &Config = get_config();
+    if !config.game_server.snapshot_enabled {
+        return Ok(());
+    }
+    let directory: &Path = &config.game_server.snapshot_path;
+    tokio::fs::create_dir_all(directory)
+        .await
+        .map_err(Error::snapshot_file)?;
+    let content: Vec<u8> = serde_json::to_vec(record)?;
+    let file: PathBuf = get_snapshot_file(directory, record.game_id);
+    let temp_file: PathBuf = file.with_extension("tmp");
+    tokio::fs::write(&temp_file, content)
+        .await
+        .map_err(Error::snapshot_file)?;
+    tokio::fs::rename(&temp_file, &file)
+        .await
+        .map_err(Error::snapshot_file)?;
+    Ok(())
+}
+
+pub async fn load_game_snapshots() -> Result<Vec<GameSnapshotRecord>> {
+    let config: &Config = get_config();
+    if !config.game_server.snapshot_enabled {
+        return Ok(vec![]);
+    }
+    let mut entries: tokio::fs::ReadDir =
+        match tokio::fs::read_dir(&config.game_server.snapshot_path).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(Error::snapshot_file(err)),
+        };
+    let mut records: Vec<GameSnapshotRecord> = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(Error::snapshot_file)? {
+        let path: PathBuf = entry.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some(SNAPSHOT_EXTENSION) {
+            continue;
+        }
+        let content: Vec<u8> = tokio::fs::read(&path).await.map_err(Error::snapshot_file)?;
+        match serde_json::from_slice::<GameSnapshotRecord>(&content) {
+            Ok(record) => records.push(record),
+            Err(err) => eprintln!("Skipping unreadable game snapshot {path:?}: {err}"),
+        }
+    }
+    Ok(records)
+}
+
+pub async fn remove_game_snapshot(game_id: GameId) -> Result<()> {
+    let config: &Config = get_config();
+    if !config.game_server.snapshot_enabled {
+        return Ok(());
+    }
+    let file: PathBuf = get_snapshot_file(&config.game_server.snapshot_path, game_id);
+    match tokio::fs::remove_file(file).await {
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(Error::snapshot_file(err)),
+    }
+}
@@ -0,0 +1,177 @@
+use futures::FutureExt;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, SystemTime},
+};
+
+use crate::prelude::*;
+
+const FAILURE_HISTORY_SIZE: usize = 50;
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct GameFailure {
+    pub game_id: GameId,
+    pub game_type: String,
+    pub player_ids: Vec<PlayerId>,
+    pub status: GameStatus,
+    pub reason: String,
+    pub panicked: bool,
+    pub failed_at: SystemTime,
+}
+
+pub enum GameOutcome {
+    Completed,
+    Ended,
+    Suspended,
+    TimedOut,
+    Failed(GameFailure),
+}
+
+/// Owns the lifecycle of running game tasks, so that a panic or error inside a game is
+/// reported to its players instead of silently killing the task.
+#[derive(Clone, Default)]
+pub struct GameSupervisor {
+    crashed_games: Arc<AtomicUsize>,
+    failures: Arc<Mutex<VecDeque<GameFailure>>>,
+}
+
+impl GameSupervisor {
+    pub async fn run(
+        &self,
+        game: &mut BoxGame,
+        game_type: &str,
+        duration: Duration,
+    ) -> GameOutcome {
+        let result: Result<std::thread::Result<Result<()>>, tokio::time::error::Elapsed> =
+            tokio::time::timeout(duration, AssertUnwindSafe(game.start_game()).catch_unwind())
+                .await;
+        let (reason, panicked) = match result {
+            Ok(Ok(Ok(_))) => return GameOutcome::Completed,
+            Ok(Ok(Err(err))) => match stopped_outcome(game.get_status()) {
+                Some(outcome) => return outcome,
+                None => (err.to_string(), false),
+            },
+            Ok(Err(payload)) => (panic_message(payload.as_ref()), true),
+            Err(_) => return GameOutcome::TimedOut,
+        };
+        let failure: GameFailure = GameFailure {
+            game_id: game.get_id(),
+            game_type: game_type.to_string(),
+            player_ids: game.get_player_ids(),
+            status: game.get_status().clone(),
+            reason,
+            panicked,
+            failed_at: SystemTime::now(),
+        };
+        let error: String = if panicked {
+            "The game crashed unexpectedly".to_string()
+        } else {
+            failure.reason.clone()
+        };
+        let _ = game
+            ._broadcast_message(BroadcastMessage::GameError { error })
+            .await;
+        for player_id in &failure.player_ids {
+            let _ = game.close_player_connection(*player_id).await;
+        }
+        self.record_failure(failure.clone());
+        GameOutcome::Failed(failure)
+    }
+
+    fn record_failure(&self, failure: GameFailure) {
+        let kind: &str = if failure.panicked {
+            "panicked"
+        } else {
+            "failed"
+        };
+        eprintln!(
+            "Game {} ({}) {kind} while {:?} with players {:?}: {}",
+            failure.game_id, failure.game_type, failure.status, failure.player_ids, failure.reason
+        );
+        self.crashed_games.fetch_add(1, Ordering::Relaxed);
+        let mut failures: MutexGuard<VecDeque<GameFailure>> =
+            self.failures.lock().unwrap_or_else(|err| err.into_inner());
+        failures.push_back(failure);
+        while failures.len() > FAILURE_HISTORY_SIZE {
+            failures.pop_front();
+        }
+    }
+
+    pub fn get_crashed_games_count(&self) -> usize {
+        self.crashed_games.load(Ordering::Relaxed)
+    }
+
+    pub fn get_recent_failures(&self) -> Vec<GameFailure> {
+        self.failures
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+/// Games that stop on purpose unwind with an error too: a forfeit finishes the game, while
+/// admins, timeout policies and shutdowns end or suspend it. Only other statuses are failures.
+fn stopped_outcome(status: &GameStatus) -> Option<GameOutcome> {
+    match status {
+        GameStatus::Finished => Some(GameOutcome::Completed),
+        GameStatus::Ended => Some(GameOutcome::Ended),
+        GameStatus::Suspended => Some(GameOutcome::Suspended),
+        GameStatus::NotStarted | GameStatus::Started => None,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forfeited_game_counts_as_completed() {
+        assert!(matches!(
+            stopped_outcome(&GameStatus::Finished),
+            Some(GameOutcome::Completed)
+        ));
+    }
+
+    #[test]
+    fn ended_and_suspended_games_are_not_failures() {
+        assert!(matches!(
+            stopped_outcome(&GameStatus::Ended),
+            Some(GameOutcome::Ended)
+        ));
+        assert!(matches!(
+            stopped_outcome(&GameStatus::Suspended),
+            Some(GameOutcome::Suspended)
+        ));
+    }
+
+    #[test]
+    fn errors_while_running_are_failures() {
+        assert!(stopped_outcome(&GameStatus::Started).is_none());
+        assert!(stopped_outcome(&GameStatus::NotStarted).is_none());
+    }
+
+    #[test]
+    fn panic_payloads_are_described() {
+        assert_eq!(panic_message(&"boom"), "boom");
+        assert_eq!(panic_message(&"boom".to_string()), "boom");
+        assert_eq!(panic_message(&42), "unknown panic");
+    }
+}
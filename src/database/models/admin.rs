@@ -1,7 +1,7 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::core::UserId;
+use crate::{auth::Permission, core::UserId};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Admin {
@@ -17,13 +17,25 @@ pub struct Admin {
     pub permissions: Vec<String>,
 }
 
+impl Admin {
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.is_active
+            && self
+                .permissions
+                .iter()
+                .any(|granted: &String| granted == permission.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AdminInfo {
     pub id: UserId,
     pub email: String,
     pub username: String,
+    pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub last_login: Option<DateTime<Utc>>,
+    pub permissions: Vec<String>,
 }
 
 impl From<Admin> for AdminInfo {
@@ -32,10 +44,57 @@ impl From<Admin> for AdminInfo {
             id: user.id,
             email: user.email,
             username: user.username,
+            is_active: user.is_active,
             created_at: DateTime::from_naive_utc_and_offset(user.created_at, Utc),
             last_login: user
                 .last_login
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            permissions: user.permissions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admin(permissions: &[&str], is_active: bool) -> Admin {
+        let now: NaiveDateTime = Utc::now().naive_utc();
+        Admin {
+            id: uuid::Uuid::new_v4(),
+            email: "admin@example.com".to_string(),
+            password_hash: String::new(),
+            username: "admin".to_string(),
+            email_verified: true,
+            is_active,
+            created_at: now,
+            updated_at: now,
+            last_login: None,
+            permissions: permissions.iter().map(ToString::to_string).collect(),
         }
     }
+
+    #[test]
+    fn admins_only_have_granted_permissions() {
+        let admin: Admin = admin(&["users:read", "games:manage"], true);
+        assert!(admin.has_permission(Permission::UsersRead));
+        assert!(admin.has_permission(Permission::GamesManage));
+        assert!(!admin.has_permission(Permission::UsersLock));
+        assert!(!admin.has_permission(Permission::AdminsManage));
+    }
+
+    #[test]
+    fn inactive_admins_have_no_permissions() {
+        let admin: Admin = admin(&["users:read", "admins:manage"], false);
+        assert!(!admin.has_permission(Permission::UsersRead));
+        assert!(!admin.has_permission(Permission::AdminsManage));
+    }
+
+    #[test]
+    fn unknown_permission_strings_grant_nothing() {
+        let admin: Admin = admin(&["users:*", "admins"], true);
+        assert!(Permission::ALL
+            .iter()
+            .all(|permission: &Permission| !admin.has_permission(*permission)));
+    }
 }
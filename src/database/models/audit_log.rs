@@ -0,0 +1,105 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::core::UserId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    UserLock,
+    UserUnlock,
+    UserDelete,
+    UserBan,
+    BanLift,
+    ReportAction,
+    ReportDismiss,
+    GameEnd,
+    PlayerKick,
+    SessionClear,
+    StaleSessionsClear,
+    AdminCreate,
+    AdminDelete,
+    AdminPasswordReset,
+    PermissionGrant,
+    PermissionRevoke,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::UserLock => "user.lock",
+            AuditAction::UserUnlock => "user.unlock",
+            AuditAction::UserDelete => "user.delete",
+            AuditAction::UserBan => "user.ban",
+            AuditAction::BanLift => "user.lift_ban",
+            AuditAction::ReportAction => "report.action",
+            AuditAction::ReportDismiss => "report.dismiss",
+            AuditAction::GameEnd => "game.end",
+            AuditAction::PlayerKick => "player.kick",
+            AuditAction::SessionClear => "session.clear",
+            AuditAction::StaleSessionsClear => "session.clear_stale",
+            AuditAction::AdminCreate => "admin.create",
+            AuditAction::AdminDelete => "admin.delete",
+            AuditAction::AdminPasswordReset => "admin.reset_password",
+            AuditAction::PermissionGrant => "admin.grant_permission",
+            AuditAction::PermissionRevoke => "admin.revoke_permission",
+        }
+    }
+
+    pub fn target_type(&self) -> &'static str {
+        match self {
+            AuditAction::UserLock
+            | AuditAction::UserUnlock
+            | AuditAction::UserDelete
+            | AuditAction::UserBan
+            | AuditAction::BanLift => "user",
+            AuditAction::ReportAction | AuditAction::ReportDismiss => "report",
+            AuditAction::GameEnd => "game",
+            AuditAction::PlayerKick | AuditAction::SessionClear => "player",
+            AuditAction::StaleSessionsClear => "registry",
+            AuditAction::AdminCreate
+            | AuditAction::AdminDelete
+            | AuditAction::AdminPasswordReset
+            | AuditAction::PermissionGrant
+            | AuditAction::PermissionRevoke => "admin",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub admin_id: UserId,
+    pub admin_username: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub reason: Option<String>,
+    pub details: serde_json::Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_path: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditLogEntry {
+    pub admin_id: UserId,
+    pub admin_username: String,
+    pub action: AuditAction,
+    pub target_id: Option<String>,
+    pub reason: Option<String>,
+    pub details: serde_json::Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_path: String,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditLogFilter {
+    pub admin_id: Option<UserId>,
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{core::UserId, errors::Error};
+
+/// What a ban keeps the user from doing. A login ban covers every other scope too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BanScope {
+    Login,
+    Matchmaking,
+    Chat,
+}
+
+impl BanScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BanScope::Login => "login",
+            BanScope::Matchmaking => "matchmaking",
+            BanScope::Chat => "chat",
+        }
+    }
+
+    /// The stored scopes that block this one.
+    pub fn covering_scopes(&self) -> Vec<String> {
+        match self {
+            BanScope::Login => vec![BanScope::Login.to_string()],
+            scope => vec![BanScope::Login.to_string(), scope.to_string()],
+        }
+    }
+}
+
+impl fmt::Display for BanScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BanScope {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "login" => Ok(BanScope::Login),
+            "matchmaking" => Ok(BanScope::Matchmaking),
+            "chat" => Ok(BanScope::Chat),
+            _ => Err(Error::Validator(format!("Unknown ban scope '{value}'"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBan {
+    pub id: uuid::Uuid,
+    pub user_id: UserId,
+    pub admin_id: UserId,
+    pub scope: String,
+    pub reason: String,
+    pub starts_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub lifted_at: Option<NaiveDateTime>,
+    pub lifted_by: Option<UserId>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewUserBan {
+    pub user_id: UserId,
+    pub admin_id: UserId,
+    pub scope: BanScope,
+    pub reason: String,
+    pub starts_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl UserBan {
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.lifted_at.is_none()
+            && self.starts_at <= now
+            && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    pub fn expires_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+            .map(|expires_at| DateTime::from_naive_utc_and_offset(expires_at, Utc))
+    }
+
+    /// The message shown to the banned user.
+    pub fn notice(&self) -> String {
+        let until: String = match self.expires_at_utc() {
+            Some(expires_at) => format!("until {}", expires_at.format("%Y-%m-%d %H:%M UTC")),
+            None => "permanently".to_string(),
+        };
+        let restriction: &str = match self.scope.parse::<BanScope>() {
+            Ok(BanScope::Login) => "Your account is suspended",
+            Ok(BanScope::Matchmaking) => "You are banned from matchmaking",
+            Ok(BanScope::Chat) => "You are banned from chat",
+            Err(_) => "Your account is restricted",
+        };
+        format!("{restriction} {until}: {}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    fn ban(
+        scope: BanScope,
+        starts_at: NaiveDateTime,
+        expires_at: Option<NaiveDateTime>,
+    ) -> UserBan {
+        UserBan {
+            id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            admin_id: uuid::Uuid::new_v4(),
+            scope: scope.to_string(),
+            reason: "Abuse".to_string(),
+            starts_at,
+            expires_at,
+            lifted_at: None,
+            lifted_by: None,
+            created_at: starts_at,
+        }
+    }
+
+    #[test]
+    fn scopes_round_trip_through_their_names() {
+        for scope in [BanScope::Login, BanScope::Matchmaking, BanScope::Chat] {
+            assert_eq!(scope.to_string().parse::<BanScope>().unwrap(), scope);
+        }
+        assert!(matches!(
+            "everything".parse::<BanScope>(),
+            Err(Error::Validator(_))
+        ));
+        assert!("Login".parse::<BanScope>().is_err());
+    }
+
+    #[test]
+    fn login_bans_cover_every_scope() {
+        assert_eq!(BanScope::Login.covering_scopes(), vec!["login"]);
+        assert_eq!(
+            BanScope::Matchmaking.covering_scopes(),
+            vec!["login", "matchmaking"]
+        );
+        assert_eq!(BanScope::Chat.covering_scopes(), vec!["login", "chat"]);
+    }
+
+    #[test]
+    fn bans_are_active_between_start_and_expiry() {
+        let now: NaiveDateTime = Utc::now().naive_utc();
+        let hour: TimeDelta = TimeDelta::hours(1);
+        assert!(ban(BanScope::Login, now - hour, Some(now + hour)).is_active(now));
+        assert!(ban(BanScope::Login, now, None).is_active(now));
+        assert!(!ban(BanScope::Login, now + hour, None).is_active(now));
+        assert!(!ban(BanScope::Login, now - hour * 2, Some(now - hour)).is_active(now));
+        assert!(!ban(BanScope::Login, now - hour, Some(now)).is_active(now));
+    }
+
+    #[test]
+    fn lifted_bans_are_inactive() {
+        let now: NaiveDateTime = Utc::now().naive_utc();
+        let mut lifted: UserBan = ban(BanScope::Matchmaking, now - TimeDelta::hours(1), None);
+        lifted.lifted_at = Some(now);
+        assert!(!lifted.is_active(now));
+    }
+
+    #[test]
+    fn notices_describe_the_scope_and_expiry() {
+        let starts_at: NaiveDateTime = DateTime::from_timestamp(1_790_000_000, 0)
+            .unwrap()
+            .naive_utc();
+        let expires_at: NaiveDateTime = starts_at + TimeDelta::days(1);
+        assert_eq!(
+            ban(BanScope::Matchmaking, starts_at, Some(expires_at)).notice(),
+            format!(
+                "You are banned from matchmaking until {}: Abuse",
+                expires_at.format("%Y-%m-%d %H:%M UTC")
+            )
+        );
+        assert_eq!(
+            ban(BanScope::Login, starts_at, None).notice(),
+            "Your account is suspended permanently: Abuse"
+        );
+    }
+}
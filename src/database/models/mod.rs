@@ -1,5 +1,13 @@
-pub mod user;
 pub mod admin;
+pub mod audit_log;
+pub mod ban;
+pub mod refresh_token;
+pub mod report;
+pub mod user;
 
+pub use admin::*;
+pub use audit_log::*;
+pub use ban::*;
+pub use refresh_token::*;
+pub use report::*;
 pub use user::*;
-pub use admin::*;
\ No newline at end of file
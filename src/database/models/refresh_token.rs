@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+
+use crate::core::UserId;
+
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub id: uuid::Uuid,
+    pub family_id: uuid::Uuid,
+    pub user_id: UserId,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
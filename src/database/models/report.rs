@@ -0,0 +1,164 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{
+    core::{GameId, UserId},
+    errors::Error,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCategory {
+    Cheating,
+    Abuse,
+    Stalling,
+    Other,
+}
+
+impl ReportCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportCategory::Cheating => "cheating",
+            ReportCategory::Abuse => "abuse",
+            ReportCategory::Stalling => "stalling",
+            ReportCategory::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for ReportCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportCategory {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "cheating" => Ok(ReportCategory::Cheating),
+            "abuse" => Ok(ReportCategory::Abuse),
+            "stalling" => Ok(ReportCategory::Stalling),
+            "other" => Ok(ReportCategory::Other),
+            _ => Err(Error::Validator(format!(
+                "Unknown report category '{value}'"
+            ))),
+        }
+    }
+}
+
+/// Where a report stands in the moderation queue. Only open reports can be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Open,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Actioned => "actioned",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "open" => Ok(ReportStatus::Open),
+            "actioned" => Ok(ReportStatus::Actioned),
+            "dismissed" => Ok(ReportStatus::Dismissed),
+            _ => Err(Error::Validator(format!("Unknown report status '{value}'"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerReport {
+    pub id: uuid::Uuid,
+    pub game_id: GameId,
+    pub game_type: String,
+    pub reporter_id: UserId,
+    pub reported_user_id: UserId,
+    pub category: String,
+    pub description: String,
+    /// The game state and hand histories captured when the report was filed.
+    pub context: serde_json::Value,
+    pub status: String,
+    pub resolved_by: Option<UserId>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub resolution_note: Option<String>,
+    pub ban_id: Option<uuid::Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPlayerReport {
+    pub game_id: GameId,
+    pub game_type: String,
+    pub reporter_id: UserId,
+    pub reported_user_id: UserId,
+    pub category: ReportCategory,
+    pub description: String,
+    pub context: serde_json::Value,
+}
+
+#[derive(Debug, Default)]
+pub struct ReportFilter {
+    pub status: Option<ReportStatus>,
+    pub category: Option<ReportCategory>,
+    pub reporter_id: Option<UserId>,
+    pub reported_user_id: Option<UserId>,
+    pub game_id: Option<GameId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_round_trip_through_their_names() {
+        for status in [
+            ReportStatus::Open,
+            ReportStatus::Actioned,
+            ReportStatus::Dismissed,
+        ] {
+            assert_eq!(status.to_string().parse::<ReportStatus>().unwrap(), status);
+        }
+        assert!(matches!(
+            "resolved".parse::<ReportStatus>(),
+            Err(Error::Validator(_))
+        ));
+        assert!("Open".parse::<ReportStatus>().is_err());
+    }
+
+    #[test]
+    fn categories_round_trip_through_their_names() {
+        for category in [
+            ReportCategory::Cheating,
+            ReportCategory::Abuse,
+            ReportCategory::Stalling,
+            ReportCategory::Other,
+        ] {
+            assert_eq!(
+                category.to_string().parse::<ReportCategory>().unwrap(),
+                category
+            );
+        }
+        assert!("griefing".parse::<ReportCategory>().is_err());
+    }
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::UserId;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct User {
     pub id: UserId,
     pub email: String,
@@ -41,3 +41,126 @@ impl From<User> for UserInfo {
         }
     }
 }
+
+/// The admin-facing view of a user, which leaves out credentials.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserView {
+    pub id: UserId,
+    pub email: String,
+    pub username: String,
+    pub email_verified: bool,
+    pub is_active: bool,
+    pub is_locked: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_login: Option<DateTime<Utc>>,
+    pub games_played: i32,
+    pub games_won: i32,
+}
+
+impl From<User> for AdminUserView {
+    fn from(user: User) -> Self {
+        AdminUserView {
+            id: user.id,
+            email: user.email,
+            username: user.username,
+            email_verified: user.email_verified,
+            is_active: user.is_active,
+            is_locked: user.is_locked,
+            created_at: DateTime::from_naive_utc_and_offset(user.created_at, Utc),
+            updated_at: DateTime::from_naive_utc_and_offset(user.updated_at, Utc),
+            last_login: user
+                .last_login
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            games_played: user.games_played,
+            games_won: user.games_won,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UserSearchFilter {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub is_locked: Option<bool>,
+    pub is_active: Option<bool>,
+    pub email_verified: Option<bool>,
+    pub created_after: Option<NaiveDateTime>,
+    pub created_before: Option<NaiveDateTime>,
+    pub last_login_after: Option<NaiveDateTime>,
+    pub last_login_before: Option<NaiveDateTime>,
+    pub min_games_played: Option<i32>,
+    pub max_games_played: Option<i32>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    #[default]
+    CreatedAt,
+    Username,
+    LastLogin,
+    GamesPlayed,
+}
+
+impl UserSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt => "created_at",
+            UserSortField::Username => "username",
+            UserSortField::LastLogin => "COALESCE(last_login, TIMESTAMP 'epoch')",
+            UserSortField::GamesPlayed => "games_played",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_fields_map_to_their_columns() {
+        assert_eq!(UserSortField::default().column(), "created_at");
+        assert_eq!(UserSortField::Username.column(), "username");
+        assert_eq!(UserSortField::GamesPlayed.column(), "games_played");
+        // Users who never logged in sort as the oldest logins instead of as NULLs.
+        assert_eq!(
+            UserSortField::LastLogin.column(),
+            "COALESCE(last_login, TIMESTAMP 'epoch')"
+        );
+    }
+
+    #[test]
+    fn sort_options_are_read_from_snake_case() {
+        assert!(matches!(
+            serde_json::from_str::<UserSortField>("\"last_login\"").unwrap(),
+            UserSortField::LastLogin
+        ));
+        assert!(serde_json::from_str::<UserSortField>("\"password_hash\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<SortOrder>("\"asc\"")
+                .unwrap()
+                .as_sql(),
+            "ASC"
+        );
+        assert_eq!(SortOrder::default().as_sql(), "DESC");
+        assert!(serde_json::from_str::<SortOrder>("\"ASC\"").is_err());
+    }
+}
@@ -20,16 +20,18 @@ impl AdminRepository {
         email: &str,
         username: &str,
         password_hash: &str,
+        permissions: &[String],
     ) -> Result<Admin> {
         let row = sqlx::query!(
             r#"
-            INSERT INTO admins (email, username, password_hash)
-            VALUES ($1, $2, $3)
+            INSERT INTO admins (email, username, password_hash, permissions)
+            VALUES ($1, $2, $3, $4)
             RETURNING id, email, password_hash, username, email_verified, is_active, created_at, updated_at, last_login, permissions
             "#,
             email,
             username,
-            password_hash
+            password_hash,
+            permissions
         )
         .fetch_one(&self.pool)
         .await
@@ -88,6 +90,34 @@ impl AdminRepository {
         }))
     }
 
+    pub async fn list_admins(&self) -> Result<Vec<Admin>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, email, password_hash, username, email_verified, is_active, created_at, updated_at, last_login, permissions
+            FROM admins
+            ORDER BY created_at
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to list admins: {err}")))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Admin {
+                id: row.id,
+                email: row.email,
+                password_hash: row.password_hash,
+                username: row.username,
+                email_verified: row.email_verified,
+                is_active: row.is_active,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                last_login: row.last_login,
+                permissions: row.permissions,
+            })
+            .collect())
+    }
+
     pub async fn get_admin_by_username(&self, username: String) -> Result<Option<Admin>> {
         let row = sqlx::query!(
             r#"
@@ -121,7 +151,9 @@ impl AdminRepository {
         )
         .fetch_one(&self.pool)
         .await
-        .map_err(|err: SqlxError| Error::Database(format!("Failed to check email existence: {err}")))?;
+        .map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to check email existence: {err}"))
+        })?;
         Ok(count.count.unwrap_or(0) > 0)
     }
 
@@ -167,6 +199,54 @@ impl AdminRepository {
         Ok(())
     }
 
+    pub async fn grant_permission(
+        &self,
+        admin_id: UserId,
+        permission: &str,
+    ) -> Result<Vec<String>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE admins
+            SET permissions = CASE
+                    WHEN $1 = ANY(permissions) THEN permissions
+                    ELSE array_append(permissions, $1)
+                END,
+                updated_at = NOW()
+            WHERE id = $2 AND is_active = true
+            RETURNING permissions
+            "#,
+            permission,
+            admin_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to grant permission: {err}")))?;
+        row.map(|row| row.permissions)
+            .ok_or(Error::UserIdNotFound(admin_id))
+    }
+
+    pub async fn revoke_permission(
+        &self,
+        admin_id: UserId,
+        permission: &str,
+    ) -> Result<Vec<String>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE admins
+            SET permissions = array_remove(permissions, $1), updated_at = NOW()
+            WHERE id = $2 AND is_active = true
+            RETURNING permissions
+            "#,
+            permission,
+            admin_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to revoke permission: {err}")))?;
+        row.map(|row| row.permissions)
+            .ok_or(Error::UserIdNotFound(admin_id))
+    }
+
     pub async fn update_last_login(&self, admin_id: UserId) -> Result<()> {
         let result: PgQueryResult = sqlx::query!(
             "UPDATE admins SET last_login = NOW(), updated_at = NOW() WHERE id = $1 AND is_active = true",
@@ -0,0 +1,112 @@
+use sqlx::Error as SqlxError;
+
+use crate::database::{AuditLogEntry, AuditLogFilter, NewAuditLogEntry};
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct AuditLogRepository {
+    pool: PgPool,
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, entry: &NewAuditLogEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin_audit_log (admin_id, admin_username, action, target_type, target_id, reason, details, ip_address, user_agent, request_path)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            entry.admin_id,
+            entry.admin_username,
+            entry.action.as_str(),
+            entry.action.target_type(),
+            entry.target_id,
+            entry.reason,
+            entry.details,
+            entry.ip_address,
+            entry.user_agent,
+            entry.request_path
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to record audit log entry: {err}")))?;
+        Ok(())
+    }
+
+    /// Returns one page of matching entries, newest first, together with the total number
+    /// of entries matching the filter.
+    pub async fn list(
+        &self,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AuditLogEntry>, i64)> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, admin_id, admin_username, action, target_type, target_id, reason, details, ip_address, user_agent, request_path, created_at
+            FROM admin_audit_log
+            WHERE ($1::uuid IS NULL OR admin_id = $1)
+                AND ($2::text IS NULL OR action = $2)
+                AND ($3::text IS NULL OR target_type = $3)
+                AND ($4::text IS NULL OR target_id = $4)
+                AND ($5::timestamp IS NULL OR created_at >= $5)
+                AND ($6::timestamp IS NULL OR created_at < $6)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $7 OFFSET $8
+            "#,
+            filter.admin_id,
+            filter.action,
+            filter.target_type,
+            filter.target_id,
+            filter.from,
+            filter.to,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to list audit log: {err}")))?;
+        let entries: Vec<AuditLogEntry> = rows
+            .into_iter()
+            .map(|row| AuditLogEntry {
+                id: row.id,
+                admin_id: row.admin_id,
+                admin_username: row.admin_username,
+                action: row.action,
+                target_type: row.target_type,
+                target_id: row.target_id,
+                reason: row.reason,
+                details: row.details,
+                ip_address: row.ip_address,
+                user_agent: row.user_agent,
+                request_path: row.request_path,
+                created_at: row.created_at,
+            })
+            .collect();
+        let total = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM admin_audit_log
+            WHERE ($1::uuid IS NULL OR admin_id = $1)
+                AND ($2::text IS NULL OR action = $2)
+                AND ($3::text IS NULL OR target_type = $3)
+                AND ($4::text IS NULL OR target_id = $4)
+                AND ($5::timestamp IS NULL OR created_at >= $5)
+                AND ($6::timestamp IS NULL OR created_at < $6)
+            "#,
+            filter.admin_id,
+            filter.action,
+            filter.target_type,
+            filter.target_id,
+            filter.from,
+            filter.to
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to count audit log: {err}")))?;
+        Ok((entries, total.count.unwrap_or(0)))
+    }
+}
@@ -1,5 +1,9 @@
 pub mod admin_repository;
+pub mod audit_log_repository;
+pub mod report_repository;
 pub mod user_repository;
 
 pub use admin_repository::*;
-pub use user_repository::*;
\ No newline at end of file
+pub use audit_log_repository::*;
+pub use report_repository::*;
+pub use user_repository::*;
@@ -0,0 +1,317 @@
+use sqlx::{Error as SqlxError, PgExecutor, Postgres, Transaction};
+
+use crate::database::{
+    NewPlayerReport, NewUserBan, PlayerReport, ReportCategory, ReportFilter, ReportStatus, UserBan,
+    UserRepository,
+};
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct ReportRepository {
+    pool: PgPool,
+}
+
+impl ReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Files a report, or returns `None` when the reporter already has an open report
+    /// against the same player in the same game.
+    pub async fn create_report(&self, report: &NewPlayerReport) -> Result<Option<PlayerReport>> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO player_reports (game_id, game_type, reporter_id, reported_user_id, category, description, context)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (reporter_id, reported_user_id, game_id) WHERE status = 'open' DO NOTHING
+            RETURNING id, game_id, game_type, reporter_id, reported_user_id, category, description, context, status, resolved_by, resolved_at, resolution_note, ban_id, created_at
+            "#,
+            report.game_id,
+            report.game_type,
+            report.reporter_id,
+            report.reported_user_id,
+            report.category.as_str(),
+            report.description,
+            report.context
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to create report: {err}")))?;
+        Ok(row.map(|row| PlayerReport {
+            id: row.id,
+            game_id: row.game_id,
+            game_type: row.game_type,
+            reporter_id: row.reporter_id,
+            reported_user_id: row.reported_user_id,
+            category: row.category,
+            description: row.description,
+            context: row.context,
+            status: row.status,
+            resolved_by: row.resolved_by,
+            resolved_at: row.resolved_at,
+            resolution_note: row.resolution_note,
+            ban_id: row.ban_id,
+            created_at: row.created_at,
+        }))
+    }
+
+    pub async fn get_report(&self, report_id: uuid::Uuid) -> Result<Option<PlayerReport>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, game_id, game_type, reporter_id, reported_user_id, category, description, context, status, resolved_by, resolved_at, resolution_note, ban_id, created_at
+            FROM player_reports
+            WHERE id = $1
+            "#,
+            report_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to get report: {err}")))?;
+        Ok(row.map(|row| PlayerReport {
+            id: row.id,
+            game_id: row.game_id,
+            game_type: row.game_type,
+            reporter_id: row.reporter_id,
+            reported_user_id: row.reported_user_id,
+            category: row.category,
+            description: row.description,
+            context: row.context,
+            status: row.status,
+            resolved_by: row.resolved_by,
+            resolved_at: row.resolved_at,
+            resolution_note: row.resolution_note,
+            ban_id: row.ban_id,
+            created_at: row.created_at,
+        }))
+    }
+
+    /// Returns one page of matching reports, oldest first so the queue is worked in the
+    /// order reports came in, together with the total number of matching reports.
+    pub async fn list_reports(
+        &self,
+        filter: &ReportFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<PlayerReport>, i64)> {
+        let status: Option<&str> = filter.status.map(|status: ReportStatus| status.as_str());
+        let category: Option<&str> = filter
+            .category
+            .map(|category: ReportCategory| category.as_str());
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, game_id, game_type, reporter_id, reported_user_id, category, description, context, status, resolved_by, resolved_at, resolution_note, ban_id, created_at
+            FROM player_reports
+            WHERE ($1::text IS NULL OR status = $1)
+                AND ($2::text IS NULL OR category = $2)
+                AND ($3::uuid IS NULL OR reporter_id = $3)
+                AND ($4::uuid IS NULL OR reported_user_id = $4)
+                AND ($5::uuid IS NULL OR game_id = $5)
+            ORDER BY created_at ASC, id ASC
+            LIMIT $6 OFFSET $7
+            "#,
+            status,
+            category,
+            filter.reporter_id,
+            filter.reported_user_id,
+            filter.game_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to list reports: {err}")))?;
+        let reports: Vec<PlayerReport> = rows
+            .into_iter()
+            .map(|row| PlayerReport {
+                id: row.id,
+                game_id: row.game_id,
+                game_type: row.game_type,
+                reporter_id: row.reporter_id,
+                reported_user_id: row.reported_user_id,
+                category: row.category,
+                description: row.description,
+                context: row.context,
+                status: row.status,
+                resolved_by: row.resolved_by,
+                resolved_at: row.resolved_at,
+                resolution_note: row.resolution_note,
+                ban_id: row.ban_id,
+                created_at: row.created_at,
+            })
+            .collect();
+        let total = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM player_reports
+            WHERE ($1::text IS NULL OR status = $1)
+                AND ($2::text IS NULL OR category = $2)
+                AND ($3::uuid IS NULL OR reporter_id = $3)
+                AND ($4::uuid IS NULL OR reported_user_id = $4)
+                AND ($5::uuid IS NULL OR game_id = $5)
+            "#,
+            status,
+            category,
+            filter.reporter_id,
+            filter.reported_user_id,
+            filter.game_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to count reports: {err}")))?;
+        Ok((reports, total.count.unwrap_or(0)))
+    }
+
+    /// Closes an open report. Returns `None` when the report does not exist or was already
+    /// resolved.
+    pub async fn resolve_report(
+        &self,
+        report_id: uuid::Uuid,
+        admin_id: UserId,
+        status: ReportStatus,
+        note: &str,
+        ban_id: Option<uuid::Uuid>,
+    ) -> Result<Option<PlayerReport>> {
+        Self::update_resolution(&self.pool, report_id, admin_id, status, note, ban_id).await
+    }
+
+    /// Bans the reported player and closes the report as actioned in one transaction. Returns
+    /// `None`, without storing the ban, when the report was resolved in the meantime.
+    pub async fn resolve_report_with_ban(
+        &self,
+        report_id: uuid::Uuid,
+        ban: &NewUserBan,
+    ) -> Result<Option<(PlayerReport, UserBan)>> {
+        let mut transaction: Transaction<'_, Postgres> =
+            self.pool.begin().await.map_err(|err: SqlxError| {
+                Error::Database(format!("Failed to start transaction: {err}"))
+            })?;
+        let ban: UserBan = UserRepository::insert_ban(&mut *transaction, ban).await?;
+        let report: Option<PlayerReport> = Self::update_resolution(
+            &mut *transaction,
+            report_id,
+            ban.admin_id,
+            ReportStatus::Actioned,
+            &ban.reason,
+            Some(ban.id),
+        )
+        .await?;
+        let Some(report) = report else {
+            transaction.rollback().await.map_err(|err: SqlxError| {
+                Error::Database(format!("Failed to roll back transaction: {err}"))
+            })?;
+            return Ok(None);
+        };
+        transaction.commit().await.map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to commit transaction: {err}"))
+        })?;
+        Ok(Some((report, ban)))
+    }
+
+    async fn update_resolution<'e, E: PgExecutor<'e>>(
+        executor: E,
+        report_id: uuid::Uuid,
+        admin_id: UserId,
+        status: ReportStatus,
+        note: &str,
+        ban_id: Option<uuid::Uuid>,
+    ) -> Result<Option<PlayerReport>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE player_reports
+            SET status = $3, resolved_by = $2, resolved_at = NOW(), resolution_note = $4, ban_id = $5
+            WHERE id = $1 AND status = 'open'
+            RETURNING id, game_id, game_type, reporter_id, reported_user_id, category, description, context, status, resolved_by, resolved_at, resolution_note, ban_id, created_at
+            "#,
+            report_id,
+            admin_id,
+            status.as_str(),
+            note,
+            ban_id
+        )
+        .fetch_optional(executor)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to resolve report: {err}")))?;
+        Ok(row.map(|row| PlayerReport {
+            id: row.id,
+            game_id: row.game_id,
+            game_type: row.game_type,
+            reporter_id: row.reporter_id,
+            reported_user_id: row.reported_user_id,
+            category: row.category,
+            description: row.description,
+            context: row.context,
+            status: row.status,
+            resolved_by: row.resolved_by,
+            resolved_at: row.resolved_at,
+            resolution_note: row.resolution_note,
+            ban_id: row.ban_id,
+            created_at: row.created_at,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{AdminRepository, BanScope};
+
+    #[sqlx::test]
+    async fn bans_are_only_kept_for_reports_that_were_still_open(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool.clone());
+        let report_repository: ReportRepository = ReportRepository::new(pool.clone());
+        let admin_id: UserId = AdminRepository::new(pool)
+            .create_admin("mod@example.com", "moderator", "hash", &[])
+            .await
+            .unwrap()
+            .id;
+        let reporter_id: UserId = user_repository
+            .create_user("reporter@example.com", "reporter", "hash")
+            .await
+            .unwrap()
+            .id;
+        let reported_user_id: UserId = user_repository
+            .create_user("reported@example.com", "reported", "hash")
+            .await
+            .unwrap()
+            .id;
+        let report: PlayerReport = report_repository
+            .create_report(&NewPlayerReport {
+                game_id: uuid::Uuid::new_v4(),
+                game_type: "Qafoon".to_string(),
+                reporter_id,
+                reported_user_id,
+                category: ReportCategory::Abuse,
+                description: String::new(),
+                context: serde_json::json!({}),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let ban: NewUserBan = NewUserBan {
+            user_id: reported_user_id,
+            admin_id,
+            scope: BanScope::Chat,
+            reason: "Abuse".to_string(),
+            starts_at: None,
+            expires_at: None,
+        };
+        let (actioned, stored): (PlayerReport, UserBan) = report_repository
+            .resolve_report_with_ban(report.id, &ban)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(actioned.status, ReportStatus::Actioned.as_str());
+        assert_eq!(actioned.ban_id, Some(stored.id));
+        assert!(report_repository
+            .resolve_report_with_ban(report.id, &ban)
+            .await
+            .unwrap()
+            .is_none());
+        let bans: Vec<UserBan> = user_repository
+            .list_user_bans(reported_user_id)
+            .await
+            .unwrap();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].id, stored.id);
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(dead_code)]
 
-use sqlx::{postgres::PgQueryResult, Error as SqlxError};
+use chrono::NaiveDateTime;
+use sqlx::{postgres::PgQueryResult, Error as SqlxError, PgExecutor, Postgres, QueryBuilder};
 
-use crate::database::User;
+use crate::database::{
+    BanScope, NewUserBan, RefreshToken, SortOrder, User, UserBan, UserSearchFilter, UserSortField,
+};
 use crate::prelude::*;
 
 #[derive(Clone)]
@@ -120,6 +123,92 @@ impl UserRepository {
         }))
     }
 
+    /// Returns up to `limit` users matching the filter in the requested order. The cursor is
+    /// the id of the last user of the previous page; paging resumes right after that user.
+    pub async fn search_users(
+        &self,
+        filter: &UserSearchFilter,
+        sort: UserSortField,
+        order: SortOrder,
+        cursor: Option<UserId>,
+        limit: i64,
+    ) -> Result<Vec<User>> {
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT id, email, password_hash, username, email_verified, is_active, is_locked, created_at, updated_at, last_login, games_played, games_won FROM users WHERE TRUE",
+        );
+        if let Some(username) = &filter.username {
+            query
+                .push(" AND username ILIKE ")
+                .push_bind(contains_pattern(username));
+        }
+        if let Some(email) = &filter.email {
+            query
+                .push(" AND email ILIKE ")
+                .push_bind(contains_pattern(email));
+        }
+        if let Some(is_locked) = filter.is_locked {
+            query.push(" AND is_locked = ").push_bind(is_locked);
+        }
+        if let Some(is_active) = filter.is_active {
+            query.push(" AND is_active = ").push_bind(is_active);
+        }
+        if let Some(email_verified) = filter.email_verified {
+            query
+                .push(" AND email_verified = ")
+                .push_bind(email_verified);
+        }
+        if let Some(created_after) = filter.created_after {
+            query.push(" AND created_at >= ").push_bind(created_after);
+        }
+        if let Some(created_before) = filter.created_before {
+            query.push(" AND created_at < ").push_bind(created_before);
+        }
+        if let Some(last_login_after) = filter.last_login_after {
+            query
+                .push(" AND last_login >= ")
+                .push_bind(last_login_after);
+        }
+        if let Some(last_login_before) = filter.last_login_before {
+            query
+                .push(" AND last_login < ")
+                .push_bind(last_login_before);
+        }
+        if let Some(min_games_played) = filter.min_games_played {
+            query
+                .push(" AND games_played >= ")
+                .push_bind(min_games_played);
+        }
+        if let Some(max_games_played) = filter.max_games_played {
+            query
+                .push(" AND games_played <= ")
+                .push_bind(max_games_played);
+        }
+        let column: &str = sort.column();
+        let direction: &str = order.as_sql();
+        if let Some(cursor) = cursor {
+            let comparison: &str = match order {
+                SortOrder::Asc => ">",
+                SortOrder::Desc => "<",
+            };
+            query
+                .push(format!(
+                    " AND ({column}, id) {comparison} (SELECT {column}, id FROM users WHERE id = "
+                ))
+                .push_bind(cursor)
+                .push(")");
+        }
+        query
+            .push(format!(
+                " ORDER BY {column} {direction}, id {direction} LIMIT "
+            ))
+            .push_bind(limit);
+        query
+            .build_query_as::<User>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err: SqlxError| Error::Database(format!("Failed to search users: {err}")))
+    }
+
     pub async fn email_exists(&self, email: &str) -> Result<bool> {
         let count = sqlx::query!(
             "SELECT COUNT(*) as count FROM users WHERE email = $1",
@@ -279,4 +368,354 @@ impl UserRepository {
         }
         Ok(())
     }
+
+    pub async fn create_ban(&self, ban: &NewUserBan) -> Result<UserBan> {
+        Self::insert_ban(&self.pool, ban).await
+    }
+
+    /// Stores a ban through `executor`, so that callers can make it part of a transaction.
+    pub(crate) async fn insert_ban<'e, E: PgExecutor<'e>>(
+        executor: E,
+        ban: &NewUserBan,
+    ) -> Result<UserBan> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO user_bans (user_id, admin_id, scope, reason, starts_at, expires_at)
+            VALUES ($1, $2, $3, $4, COALESCE($5, NOW()::timestamp), $6)
+            RETURNING id, user_id, admin_id, scope, reason, starts_at, expires_at, lifted_at, lifted_by, created_at
+            "#,
+            ban.user_id,
+            ban.admin_id,
+            ban.scope.as_str(),
+            ban.reason,
+            ban.starts_at,
+            ban.expires_at
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to create ban: {err}")))?;
+        Ok(UserBan {
+            id: row.id,
+            user_id: row.user_id,
+            admin_id: row.admin_id,
+            scope: row.scope,
+            reason: row.reason,
+            starts_at: row.starts_at,
+            expires_at: row.expires_at,
+            lifted_at: row.lifted_at,
+            lifted_by: row.lifted_by,
+            created_at: row.created_at,
+        })
+    }
+
+    /// Returns the ban that currently keeps the user out of `scope`, preferring the one that
+    /// lasts longest. Expired and lifted bans are ignored.
+    pub async fn get_active_ban(
+        &self,
+        user_id: UserId,
+        scope: BanScope,
+    ) -> Result<Option<UserBan>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_id, admin_id, scope, reason, starts_at, expires_at, lifted_at, lifted_by, created_at
+            FROM user_bans
+            WHERE user_id = $1
+                AND scope = ANY($2)
+                AND lifted_at IS NULL
+                AND starts_at <= NOW()
+                AND (expires_at IS NULL OR expires_at > NOW())
+            ORDER BY expires_at DESC NULLS FIRST
+            LIMIT 1
+            "#,
+            user_id,
+            &scope.covering_scopes()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to get active ban: {err}")))?;
+        Ok(row.map(|row| UserBan {
+            id: row.id,
+            user_id: row.user_id,
+            admin_id: row.admin_id,
+            scope: row.scope,
+            reason: row.reason,
+            starts_at: row.starts_at,
+            expires_at: row.expires_at,
+            lifted_at: row.lifted_at,
+            lifted_by: row.lifted_by,
+            created_at: row.created_at,
+        }))
+    }
+
+    pub async fn list_user_bans(&self, user_id: UserId) -> Result<Vec<UserBan>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id, admin_id, scope, reason, starts_at, expires_at, lifted_at, lifted_by, created_at
+            FROM user_bans
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to list bans: {err}")))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| UserBan {
+                id: row.id,
+                user_id: row.user_id,
+                admin_id: row.admin_id,
+                scope: row.scope,
+                reason: row.reason,
+                starts_at: row.starts_at,
+                expires_at: row.expires_at,
+                lifted_at: row.lifted_at,
+                lifted_by: row.lifted_by,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    pub async fn lift_ban(&self, ban_id: uuid::Uuid, admin_id: UserId) -> Result<Option<UserBan>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE user_bans
+            SET lifted_at = NOW(), lifted_by = $2
+            WHERE id = $1 AND lifted_at IS NULL
+            RETURNING id, user_id, admin_id, scope, reason, starts_at, expires_at, lifted_at, lifted_by, created_at
+            "#,
+            ban_id,
+            admin_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to lift ban: {err}")))?;
+        Ok(row.map(|row| UserBan {
+            id: row.id,
+            user_id: row.user_id,
+            admin_id: row.admin_id,
+            scope: row.scope,
+            reason: row.reason,
+            starts_at: row.starts_at,
+            expires_at: row.expires_at,
+            lifted_at: row.lifted_at,
+            lifted_by: row.lifted_by,
+            created_at: row.created_at,
+        }))
+    }
+
+    pub async fn create_refresh_token(
+        &self,
+        token_id: uuid::Uuid,
+        family_id: uuid::Uuid,
+        user_id: UserId,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO refresh_tokens (id, family_id, user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            token_id,
+            family_id,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to create refresh token: {err}"))
+        })?;
+        Ok(())
+    }
+
+    pub async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, family_id, user_id, expires_at, used_at, revoked_at, created_at
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to get refresh token: {err}")))?;
+        Ok(row.map(|row| RefreshToken {
+            id: row.id,
+            family_id: row.family_id,
+            user_id: row.user_id,
+            expires_at: row.expires_at,
+            used_at: row.used_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }))
+    }
+
+    /// Spends a refresh token. Returns `false` when it was already used or revoked, which
+    /// includes losing a race against a concurrent refresh with the same token.
+    pub async fn use_refresh_token(&self, token_id: uuid::Uuid) -> Result<bool> {
+        let result: PgQueryResult = sqlx::query!(
+            "UPDATE refresh_tokens SET used_at = NOW() WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL",
+            token_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err: SqlxError| Error::Database(format!("Failed to use refresh token: {err}")))?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Whether access tokens issued alongside this refresh token must be rejected. Unknown
+    /// tokens count as revoked.
+    pub async fn is_refresh_token_revoked(&self, token_id: uuid::Uuid) -> Result<bool> {
+        let row = sqlx::query!(
+            "SELECT revoked_at FROM refresh_tokens WHERE id = $1",
+            token_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to check refresh token: {err}"))
+        })?;
+        Ok(row.is_none_or(|row| row.revoked_at.is_some()))
+    }
+
+    pub async fn revoke_token_family(&self, family_id: uuid::Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE family_id = $1 AND revoked_at IS NULL",
+            family_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to revoke token family: {err}"))
+        })?;
+        Ok(())
+    }
+
+    pub async fn revoke_user_tokens(&self, user_id: UserId) -> Result<()> {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err: SqlxError| {
+            Error::Database(format!("Failed to revoke user tokens: {err}"))
+        })?;
+        Ok(())
+    }
+}
+
+fn contains_pattern(value: &str) -> String {
+    let escaped: String = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_users(user_repository: &UserRepository, usernames: &[&str]) {
+        for username in usernames {
+            user_repository
+                .create_user(&format!("{username}@example.com"), username, "hash")
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn page_through(
+        user_repository: &UserRepository,
+        filter: &UserSearchFilter,
+        sort: UserSortField,
+        order: SortOrder,
+    ) -> Vec<String> {
+        let mut usernames: Vec<String> = Vec::new();
+        let mut cursor: Option<UserId> = None;
+        loop {
+            let page: Vec<User> = user_repository
+                .search_users(filter, sort, order, cursor, 2)
+                .await
+                .unwrap();
+            let Some(last) = page.last() else {
+                return usernames;
+            };
+            cursor = Some(last.id);
+            usernames.extend(page.into_iter().map(|user: User| user.username));
+        }
+    }
+
+    #[test]
+    fn contains_patterns_match_wildcards_literally() {
+        assert_eq!(contains_pattern("bob"), "%bob%");
+        assert_eq!(contains_pattern("50%_off"), "%50\\%\\_off%");
+        assert_eq!(contains_pattern("back\\slash"), "%back\\\\slash%");
+        assert_eq!(contains_pattern(""), "%%");
+    }
+
+    #[sqlx::test]
+    async fn search_cursors_page_through_every_user_once(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        create_users(&user_repository, &["carol", "alice", "erin", "bob", "dave"]).await;
+        let filter: UserSearchFilter = UserSearchFilter::default();
+        assert_eq!(
+            page_through(
+                &user_repository,
+                &filter,
+                UserSortField::Username,
+                SortOrder::Asc
+            )
+            .await,
+            vec!["alice", "bob", "carol", "dave", "erin"]
+        );
+        assert_eq!(
+            page_through(
+                &user_repository,
+                &filter,
+                UserSortField::Username,
+                SortOrder::Desc
+            )
+            .await,
+            vec!["erin", "dave", "carol", "bob", "alice"]
+        );
+        // Every user has played no games, so only the id breaks the ties between pages.
+        let mut tied: Vec<String> = page_through(
+            &user_repository,
+            &filter,
+            UserSortField::GamesPlayed,
+            SortOrder::Desc,
+        )
+        .await;
+        tied.sort();
+        assert_eq!(tied, vec!["alice", "bob", "carol", "dave", "erin"]);
+    }
+
+    #[sqlx::test]
+    async fn username_searches_do_not_treat_input_as_wildcards(pool: PgPool) {
+        let user_repository: UserRepository = UserRepository::new(pool);
+        create_users(&user_repository, &["ann_lee", "annxlee", "Ann_Marie"]).await;
+        let filter: UserSearchFilter = UserSearchFilter {
+            username: Some("ann_".to_string()),
+            ..UserSearchFilter::default()
+        };
+        let mut matches: Vec<String> = page_through(
+            &user_repository,
+            &filter,
+            UserSortField::Username,
+            SortOrder::Asc,
+        )
+        .await;
+        matches.sort();
+        assert_eq!(matches, vec!["Ann_Marie", "ann_lee"]);
+    }
 }
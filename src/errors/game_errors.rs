@@ -22,6 +22,7 @@ pub enum Error {
     SerdeJson(String),
     GameTokenExpired,
     NoValidCard,
+    Banned(String),
 }
 
 impl Error {
@@ -49,6 +50,9 @@ impl Error {
     pub fn read_file(err: IoError) -> Self {
         Self::FileOperation(format!("unable to read file error: {err}"))
     }
+    pub fn snapshot_file(err: IoError) -> Self {
+        Self::FileOperation(format!("game snapshot file error: {err}"))
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -64,7 +68,8 @@ impl std::fmt::Display for Error {
             | Error::Timeout(msg)
             | Error::Validator(msg)
             | Error::SerdeJson(msg)
-            | Error::FileOperation(msg) => {
+            | Error::FileOperation(msg)
+            | Error::Banned(msg) => {
                 write!(f, "{msg}")
             }
             Error::Config(errors) => write!(f, "{}", errors.join("\n")),
@@ -6,7 +6,7 @@
 
 #![allow(dead_code)]
 
-use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use rand::{seq::SliceRandom, Rng};
 use std::clone::Clone;
 
 /// Enumeration of different shuffling methods available
@@ -36,26 +36,32 @@ pub enum ShuffleMethod {
 ///
 /// * `items` - A mutable reference to the vector to be shuffled
 /// * `method` - The shuffling method to use
+/// * `rng` - The random number generator driving the shuffle
 ///
 /// # Type Parameters
 ///
 /// * `Item` - The type of items in the vector, must implement `Clone`
+/// * `Random` - The random number generator type
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::games::common::shuffler::{shuffle, ShuffleMethod};
+/// use cards::games::common::shuffler::{shuffle, ShuffleMethod};
 ///
 /// let mut cards = vec![1, 2, 3, 4, 5];
-/// shuffle(&mut cards, ShuffleMethod::Riffle);
+/// shuffle(&mut cards, ShuffleMethod::Hard, &mut rand::rng());
 /// ```
-pub fn shuffle<Item: Clone>(items: &mut Vec<Item>, method: ShuffleMethod) {
+pub fn shuffle<Item: Clone, Random: Rng + ?Sized>(
+    items: &mut Vec<Item>,
+    method: ShuffleMethod,
+    rng: &mut Random,
+) {
     match method {
-        ShuffleMethod::Hard => hard_shuffle(items),
-        ShuffleMethod::Riffle => riffle_shuffle(items),
-        ShuffleMethod::Cut => cut_shuffle(items),
-        ShuffleMethod::Overhand => overhand_shuffle(items),
-        ShuffleMethod::Hindu => hindu_shuffle(items),
+        ShuffleMethod::Hard => hard_shuffle(items, rng),
+        ShuffleMethod::Riffle => riffle_shuffle(items, rng),
+        ShuffleMethod::Cut => cut_shuffle(items, rng),
+        ShuffleMethod::Overhand => overhand_shuffle(items, rng),
+        ShuffleMethod::Hindu => hindu_shuffle(items, rng),
     }
 }
 
@@ -67,8 +73,9 @@ pub fn shuffle<Item: Clone>(items: &mut Vec<Item>, method: ShuffleMethod) {
 /// # Arguments
 ///
 /// * `items` - A mutable slice of items to shuffle
-fn hard_shuffle<Item>(items: &mut [Item]) {
-    items.shuffle(&mut rand::rng());
+/// * `rng` - The random number generator driving the shuffle
+fn hard_shuffle<Item, Random: Rng + ?Sized>(items: &mut [Item], rng: &mut Random) {
+    items.shuffle(rng);
 }
 
 /// Simulates a riffle shuffle by splitting the deck and interleaving cards
@@ -82,11 +89,11 @@ fn hard_shuffle<Item>(items: &mut [Item]) {
 /// # Arguments
 ///
 /// * `items` - A mutable reference to the vector to shuffle
-fn riffle_shuffle<Item: Clone>(items: &mut Vec<Item>) {
+/// * `rng` - The random number generator driving the shuffle
+fn riffle_shuffle<Item: Clone, Random: Rng + ?Sized>(items: &mut Vec<Item>, rng: &mut Random) {
     if items.len() < 2 {
         return;
     }
-    let mut rng: ThreadRng = rand::rng();
     let iterations: i32 = rng.random_range(1..=2);
     for _ in 0..iterations {
         let split_point: usize =
@@ -131,11 +138,12 @@ fn riffle_shuffle<Item: Clone>(items: &mut Vec<Item>) {
 /// # Arguments
 ///
 /// * `items` - A mutable reference to the vector to shuffle
-fn cut_shuffle<Item: Clone>(items: &mut Vec<Item>) {
+/// * `rng` - The random number generator driving the shuffle
+fn cut_shuffle<Item: Clone, Random: Rng + ?Sized>(items: &mut Vec<Item>, rng: &mut Random) {
     if items.len() < 2 {
         return;
     }
-    let cut_point: usize = rand::rng().random_range(1..items.len());
+    let cut_point: usize = rng.random_range(1..items.len());
     let bottom_half: Vec<Item> = items.drain(cut_point..).collect();
     let top_half: Vec<Item> = std::mem::take(items);
     items.extend(bottom_half);
@@ -151,11 +159,11 @@ fn cut_shuffle<Item: Clone>(items: &mut Vec<Item>) {
 /// # Arguments
 ///
 /// * `items` - A mutable reference to the vector to shuffle
-fn overhand_shuffle<Item: Clone>(items: &mut Vec<Item>) {
+/// * `rng` - The random number generator driving the shuffle
+fn overhand_shuffle<Item: Clone, Random: Rng + ?Sized>(items: &mut Vec<Item>, rng: &mut Random) {
     if items.len() < 3 {
         return;
     }
-    let mut rng: ThreadRng = rand::rng();
     let iterations: i32 = rng.random_range(3..=5);
     for _ in 0..iterations {
         let mut shuffled: Vec<Item> = Vec::with_capacity(items.len());
@@ -180,11 +188,11 @@ fn overhand_shuffle<Item: Clone>(items: &mut Vec<Item>) {
 /// # Arguments
 ///
 /// * `items` - A mutable reference to the vector to shuffle
-fn hindu_shuffle<Item: Clone>(items: &mut Vec<Item>) {
+/// * `rng` - The random number generator driving the shuffle
+fn hindu_shuffle<Item: Clone, Random: Rng + ?Sized>(items: &mut Vec<Item>, rng: &mut Random) {
     if items.len() < 3 {
         return;
     }
-    let mut rng: ThreadRng = rand::rng();
     let iterations: i32 = rng.random_range(3..=6);
     for _ in 0..iterations {
         let mut result: Vec<Item> = Vec::with_capacity(items.len());
@@ -1,3 +1,4 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::sync::Arc;
 use tokio::{
     io::{ReadHalf, WriteHalf},
@@ -6,6 +7,7 @@ use tokio::{
 };
 
 use crate::{
+    config::TimeoutPenalty,
     core::{send_message_to_player, Game},
     games::*,
     get_player, get_player_field_index, get_player_mut, get_team, get_team_mut,
@@ -33,16 +35,97 @@ impl Game for Qafoon {
         self.id
     }
 
-    fn setup_reconnection(&mut self) -> Result<Sender<(PlayerId, Stream)>> {
+    fn setup_commands(&mut self) -> Result<Sender<GameCommand>> {
         let (tx, rx) = mpsc::channel(1024);
-        self.players_reconnection_receiver = Some(rx);
+        self.command_receiver = Some(rx);
         Ok(tx)
     }
 
-    fn get_reconnection_receiver(&mut self) -> Result<&mut Receiver<(PlayerId, Stream)>> {
-        self.players_reconnection_receiver
+    fn get_command_receiver(&mut self) -> Result<&mut Receiver<GameCommand>> {
+        self.command_receiver
             .as_mut()
-            .ok_or_else(|| Error::Game("Reconnection receiver not initialized".to_string()))
+            .ok_or_else(|| Error::Game("Command receiver not initialized".to_string()))
+    }
+
+    fn get_game_snapshot(&self) -> Result<Value> {
+        Ok(serde_json::to_value(self.get_table_snapshot()?)?)
+    }
+
+    fn get_game_summary(&self) -> GameSummary {
+        GameSummary {
+            status: self.status.clone(),
+            phase: self.phase.clone(),
+            players: self
+                .players
+                .values()
+                .sorted_by_key(|player: &&Player| player.name.clone())
+                .map(|player: &Player| SeatSummary {
+                    player_id: player.id,
+                    name: player.name.clone(),
+                    team: self
+                        .teams
+                        .get(&player.team_id)
+                        .map(|team: &Team| team.name.clone()),
+                    is_bot: player.is_bot,
+                    connected: self.players_sender.contains_key(&player.id),
+                })
+                .collect(),
+            scores: self.get_teams_game_score(),
+        }
+    }
+
+    fn get_game_type(&self) -> String {
+        "Qafoon".to_string()
+    }
+
+    fn get_persisted_state(&self) -> Result<Value> {
+        Ok(serde_json::to_value(QafoonState {
+            id: self.id,
+            teams: self.teams.clone(),
+            players: self.players.clone(),
+            field: self.field.clone(),
+            starter: self.starter,
+            trick_starter: self.trick_starter,
+            hokm: self.hokm.clone(),
+            bet: self.bet.clone(),
+            bet_winner_id: self.bet_winner_id,
+            folded_cards: self.folded_cards.clone(),
+            ground: self.ground.clone(),
+            deck: self.cards.clone(),
+            rng_seed: self.rng_seed,
+            time_banks: self.time_banks.clone(),
+            status: self.status.clone(),
+            phase: self.phase.clone(),
+        })?)
+    }
+
+    fn restore_persisted_state(&mut self, state: Value) -> Result<()> {
+        let state: QafoonState = serde_json::from_value(state)?;
+        self.id = state.id;
+        self.teams = state.teams;
+        self.players = state.players;
+        self.field = state.field;
+        self.starter = state.starter;
+        self.trick_starter = state.trick_starter;
+        self.hokm = state.hokm;
+        self.bet = state.bet;
+        self.bet_winner_id = state.bet_winner_id;
+        self.folded_cards = state.folded_cards;
+        self.ground = state.ground;
+        self.cards = state.deck;
+        self.rng_seed = state.rng_seed;
+        self.time_banks = state.time_banks;
+        self.status = state.status;
+        self.phase = state.phase;
+        Ok(())
+    }
+
+    fn get_replay_buffer(&mut self) -> &mut ReplayBuffer {
+        &mut self.replay_buffer
+    }
+
+    fn get_time_banks(&mut self) -> &mut HashMap<PlayerId, std::time::Duration> {
+        &mut self.time_banks
     }
 
     fn get_player_sender(&self, player_id: PlayerId) -> Result<&Sender<CorrelatedMessage>> {
@@ -55,32 +138,99 @@ impl Game for Qafoon {
         self.player_connections.remove(&player_id)
     }
 
-    fn get_player_receiver(
+    fn get_player_and_command_receivers(
         &mut self,
         player_id: PlayerId,
-    ) -> Result<&mut Receiver<Result<GameMessage>>> {
-        self.players_receiver
+    ) -> Result<(
+        &mut Receiver<Result<GameMessage>>,
+        &mut Receiver<GameCommand>,
+    )> {
+        let receiver: &mut Receiver<Result<GameMessage>> = self
+            .players_receiver
             .get_mut(&player_id)
-            .ok_or(Error::player_not_found(player_id))
+            .ok_or(Error::player_not_found(player_id))?;
+        let commands: &mut Receiver<GameCommand> = self
+            .command_receiver
+            .as_mut()
+            .ok_or_else(|| Error::Game("Command receiver not initialized".to_string()))?;
+        Ok((receiver, commands))
     }
 
     fn get_player(&self, player_id: PlayerId) -> Result<&Player> {
         get_player!(self.players, player_id)
     }
 
+    fn get_player_mut(&mut self, player_id: PlayerId) -> Result<&mut Player> {
+        get_player_mut!(self.players, player_id)
+    }
+
+    fn get_auto_choice(&self, player_id: PlayerId, message: &GameMessage) -> Result<PlayerChoice> {
+        let player: &Player = get_player!(self.players, player_id)?;
+        let choice: PlayerChoice = match message.message_type().as_str() {
+            "Bet" => PlayerChoice::Pass,
+            "Hokm" => PlayerChoice::HokmChoice(
+                TYPES
+                    .into_iter()
+                    .max_by_key(|hokm: &Hokm| {
+                        player
+                            .cards
+                            .iter()
+                            .filter(|card: &&Card| card.type_ == *hokm)
+                            .count()
+                    })
+                    .unwrap_or_default(),
+            ),
+            "Fold" => PlayerChoice::CardChoice(
+                player
+                    .cards
+                    .iter()
+                    .min_by_key(|card: &&Card| card.ord)
+                    .cloned()
+                    .ok_or(Error::NoValidCard)?,
+            ),
+            _ => PlayerChoice::CardChoice(
+                self.get_legal_cards(player_id)?
+                    .into_iter()
+                    .filter_map(|code: String| Card::try_from(code).ok())
+                    .min_by_key(|card: &Card| card.ord)
+                    .ok_or(Error::NoValidCard)?,
+            ),
+        };
+        println!("Auto-playing {choice:?} for player {}", player.name);
+        Ok(choice)
+    }
+
     fn remove_player_channels(&mut self, player_id: PlayerId) {
         self.players_receiver.remove(&player_id);
         self.players_sender.remove(&player_id);
     }
 
+    fn remove_player_seat(&mut self, player_id: PlayerId) -> Option<Player> {
+        self.replay_buffer.logs.remove(&player_id);
+        self.time_banks.remove(&player_id);
+        self.players.remove(&player_id)
+    }
+
     fn get_player_count(&self) -> usize {
         self.players.len()
     }
 
+    fn get_pending_demand(&self) -> Option<&PendingDemand> {
+        self.pending_demand.as_ref()
+    }
+
+    fn set_pending_demand(&mut self, pending_demand: Option<PendingDemand>) {
+        self.pending_demand = pending_demand;
+    }
+
     fn get_field(&self) -> Vec<PlayerId> {
         self.field.to_vec()
     }
 
+    fn get_capacity(&self) -> usize {
+        NUMBER_OF_PLAYERS
+    }
+
     fn is_full(&self) -> bool {
         self.get_player_count() >= NUMBER_OF_PLAYERS
     }
@@ -107,7 +257,8 @@ impl Game for Qafoon {
         self.players_sender.clear();
         self.players_receiver.clear();
         self.player_connections.clear();
-        if let Some(receiver) = self.players_reconnection_receiver.take() {
+        self.replay_buffer.clear();
+        if let Some(receiver) = self.command_receiver.take() {
             drop(receiver);
         }
     }
@@ -131,20 +282,15 @@ impl Game for Qafoon {
         Ok(())
     }
 
-    async fn get_semi_state(&self) -> Result<Value> {
-        let state: tokio::sync::RwLockReadGuard<GameSharedState> = self.shared_state.read().await;
-        let json_value: Value = serde_json::to_value(state.clone())?;
-        Ok(json_value)
-    }
-
     async fn send_player_full_state(&mut self, player_id: PlayerId) -> Result<()> {
-        let mut semi_state: Value = self.get_semi_state().await?;
-        let player: &Player = self.get_player(player_id)?;
-        semi_state["player_cards"] = serde_json::to_value(code_cards(&player.cards))?;
+        let snapshot: QafoonSnapshot = self.get_player_snapshot(player_id)?;
+        let player_name: String = snapshot.player_name.clone();
         self.send_message_to_player(
             player_id,
-            player.name.clone(),
-            GameMessage::FullState { state: semi_state },
+            player_name,
+            GameMessage::FullState {
+                state: serde_json::to_value(snapshot)?,
+            },
         )
         .await
     }
@@ -162,6 +308,7 @@ impl Game for Qafoon {
     }
 
     async fn setup_teams(&mut self) -> Result<()> {
+        self.phase = GamePhase::TeamSelection;
         self.broadcast_message(BroadcastMessage::TeamSelectionStarting)
             .await?;
         tokio::time::timeout(
@@ -288,28 +435,83 @@ impl Game for Qafoon {
         Ok(handle)
     }
 
+    async fn apply_timeout_penalty(
+        &mut self,
+        player_id: PlayerId,
+        penalty: &TimeoutPenalty,
+    ) -> Result<()> {
+        let team_id: TeamId = get_player!(self.players, player_id)?.team_id;
+        match penalty {
+            TimeoutPenalty::EndGame => Ok(()),
+            TimeoutPenalty::ScoreDeduction(points) => {
+                let team: &mut Team = get_team_mut!(self.teams, team_id)?;
+                team.score = team.score.saturating_sub(*points);
+                self.update_shared_state().await?;
+                self.broadcast_message(BroadcastMessage::GameScore {
+                    teams_score: self.get_teams_game_score(),
+                })
+                .await
+            }
+            TimeoutPenalty::Forfeit => {
+                let winner_team_id: TeamId = self.get_opposing_team_id(team_id)?;
+                let game_winner: String = get_team!(self.teams, winner_team_id)?.to_string();
+                self.broadcast_message(BroadcastMessage::GameWinner {
+                    game_winner: game_winner.clone(),
+                })
+                .await?;
+                for player_id in self.get_player_ids() {
+                    let _ = self.close_player_connection(player_id).await;
+                }
+                self.phase = GamePhase::Finished;
+                self.set_status(GameStatus::Finished);
+                Err(Error::Game(format!(
+                    "Game forfeited to {game_winner} after a player ran out of time"
+                )))
+            }
+        }
+    }
+
     async fn run_game(&mut self) -> Result<()> {
-        self.set_status(GameStatus::Started);
-        self.generate_field()?;
-        self.update_shared_state().await?;
-        shuffle(&mut self.cards, ShuffleMethod::Hard);
+        // A game restored from a snapshot picks up at the start of its next trick when it was
+        // saved mid-round, and otherwise replays the round it was saved before from the shuffle.
+        let mut resuming: bool = self.resumes_mid_round();
+        if self.is_started() {
+            self.update_shared_state().await?;
+        } else {
+            self.set_status(GameStatus::Started);
+            self.generate_field()?;
+            self.update_shared_state().await?;
+            let mut rng: StdRng = self.next_rng();
+            shuffle(&mut self.cards, ShuffleMethod::Hard, &mut rng);
+        }
         while self.should_continue_game()? {
-            self.broadcast_message(BroadcastMessage::GameScore {
-                teams_score: self.get_teams_game_score(),
-            })
-            .await?;
-            self.broadcast_message(BroadcastMessage::ShufflingCards)
+            if !resuming {
+                // Saved before anything of the round happens, so that no restore sees the
+                // last trick of a round that was already scored.
+                self.phase = GamePhase::Shuffling;
+                self.save_snapshot().await;
+                self.broadcast_message(BroadcastMessage::GameScore {
+                    teams_score: self.get_teams_game_score(),
+                })
                 .await?;
-            shuffle(&mut self.cards, ShuffleMethod::Overhand);
-            let ground_cards: Vec<Card> = self.cards.drain(0..4).collect();
-            self.hand_out_cards().await?;
-            let (highest_bet, highest_bettor_id, off_team_id) =
-                self.start_betting(ground_cards).await?;
-            self.set_starter(highest_bettor_id, highest_bet).await?;
-            let mut round_starter_id: PlayerId = self.starter;
-            self.fold_first(highest_bettor_id).await?;
-            self.set_hokm(highest_bettor_id, highest_bet).await?;
-            self.update_shared_state().await?;
+                self.broadcast_message(BroadcastMessage::ShufflingCards)
+                    .await?;
+                let mut rng: StdRng = self.next_rng();
+                shuffle(&mut self.cards, ShuffleMethod::Overhand, &mut rng);
+                let ground_cards: Vec<Card> = self.cards.drain(0..4).collect();
+                self.hand_out_cards().await?;
+                let (highest_bet, highest_bettor_id, _) = self.start_betting(ground_cards).await?;
+                self.set_starter(highest_bettor_id, highest_bet).await?;
+                self.trick_starter = self.starter;
+                self.fold_first(highest_bettor_id).await?;
+                self.set_hokm(highest_bettor_id, highest_bet).await?;
+                self.update_shared_state().await?;
+                self.phase = GamePhase::Playing;
+                self.save_snapshot().await;
+            }
+            resuming = false;
+            let highest_bet: usize = self.bet.1;
+            let off_team_id: TeamId = get_player!(self.players, self.bet_winner_id)?.team_id;
             let def_team_id: TeamId = self.get_opposing_team_id(off_team_id)?;
             while self.should_continue_round(off_team_id, def_team_id, highest_bet)? {
                 self.broadcast_message(BroadcastMessage::RoundScore {
@@ -319,7 +521,7 @@ impl Game for Qafoon {
                 self.broadcast_message(BroadcastMessage::EmptyGround)
                     .await?;
                 let round_starter_index: usize =
-                    get_player_field_index!(self.field, round_starter_id)?;
+                    get_player_field_index!(self.field, self.trick_starter)?;
                 for player_id in self
                     .get_field()
                     .into_iter()
@@ -334,9 +536,10 @@ impl Game for Qafoon {
                     })
                     .await?;
                 }
-                round_starter_id = self.get_hand_collector_id()?;
-                self.collect_hand(round_starter_id)?;
+                self.trick_starter = self.get_hand_collector_id()?;
+                self.collect_hand(self.trick_starter)?;
                 self.update_shared_state().await?;
+                self.save_snapshot().await;
             }
             self.finish_round(off_team_id, def_team_id, highest_bet)
                 .await?;
@@ -347,14 +550,27 @@ impl Game for Qafoon {
     }
 }
 
+fn get_bet_options() -> Vec<String> {
+    std::iter::once("pass".to_string())
+        .chain((0..=HIGHEST_BET).map(|bet: usize| bet.to_string()))
+        .collect()
+}
+
 impl Qafoon {
     pub fn new() -> Self {
         Self {
             id: GameId::new_v4(),
+            rng_seed: rand::random(),
             ..Default::default()
         }
     }
 
+    fn next_rng(&mut self) -> StdRng {
+        let mut rng: StdRng = StdRng::seed_from_u64(self.rng_seed);
+        self.rng_seed = rng.random();
+        rng
+    }
+
     pub fn boxed_new() -> BoxGame {
         Box::new(Qafoon::new())
     }
@@ -375,6 +591,10 @@ impl Qafoon {
         Ok(())
     }
 
+    fn resumes_mid_round(&self) -> bool {
+        self.is_started() && self.phase == GamePhase::Playing
+    }
+
     fn should_continue_round(
         &self,
         off_team_id: TeamId,
@@ -431,6 +651,7 @@ impl Qafoon {
     }
 
     async fn hand_out_cards(&mut self) -> Result<()> {
+        self.phase = GamePhase::Dealing;
         self.broadcast_message(BroadcastMessage::HandingOutCards)
             .await?;
         let cards_per_player: usize = self.cards.len() / NUMBER_OF_PLAYERS;
@@ -481,15 +702,16 @@ impl Qafoon {
     }
 
     async fn fold_first(&mut self, player_id: PlayerId) -> Result<()> {
+        self.phase = GamePhase::Folding;
         let team_id: TeamId = get_player!(self.players, player_id)?.team_id;
-        let mut folded_cards: Vec<Card> = Vec::new();
         let mut message: GameMessage = GameMessage::demand(DemandMessage::Fold);
         loop {
             {
-                let player: &mut Player = get_player_mut!(self.players, player_id)?;
+                let player: &Player = get_player!(self.players, player_id)?;
                 if player.cards.len() <= 12 {
                     break;
                 }
+                message.set_demand_options(code_cards(&player.cards));
             }
             let player_choice: Result<PlayerChoice> = self
                 .get_player_choice(player_id, &mut message, false, 0)
@@ -502,7 +724,7 @@ impl Qafoon {
                         player.name.clone()
                     };
                     let card_code: String = player_choice.code();
-                    folded_cards.push(player_choice);
+                    self.folded_cards.push(player_choice);
                     let message: GameMessage = GameMessage::RemoveCard { card: card_code };
                     self.send_message_to_player(player_id, player_name, message)
                         .await?;
@@ -513,6 +735,7 @@ impl Qafoon {
                 Err(err) => return Err(Error::Game(format!("Error getting player choice: {err}"))),
             }
         }
+        let folded_cards: Vec<Card> = self.folded_cards.clone();
         get_team_mut!(self.teams, team_id)?
             .collected_hands
             .push(folded_cards);
@@ -520,8 +743,12 @@ impl Qafoon {
     }
 
     async fn set_hokm(&mut self, player_id: PlayerId, bet: usize) -> Result<()> {
+        self.phase = GamePhase::ChoosingHokm;
         let hokms: &[Hokm] = if bet == HIGHEST_BET { &HOKMS } else { &TYPES };
-        let mut message: GameMessage = GameMessage::demand(DemandMessage::Hokm);
+        let mut message: GameMessage = GameMessage::demand_with_options(
+            DemandMessage::Hokm,
+            hokms.iter().map(Hokm::code).collect(),
+        );
         loop {
             let player_choice: Result<PlayerChoice> = self
                 .get_player_choice(player_id, &mut message, false, hokms.len() - 1)
@@ -597,6 +824,125 @@ impl Qafoon {
             .ok_or(Error::NoValidCard)
     }
 
+    fn get_rule_variant(&self) -> String {
+        match self.hokm {
+            Hokm::Default => "Undecided".to_string(),
+            Hokm::Naras | Hokm::Saras | Hokm::TakNaras => self.hokm.name().to_string(),
+            _ => "Hokm".to_string(),
+        }
+    }
+
+    fn get_player_snapshot(&self, player_id: PlayerId) -> Result<QafoonSnapshot> {
+        let player: &Player = get_player!(self.players, player_id)?;
+        let mut table: TableSnapshot = self.get_table_snapshot()?;
+        if player_id == self.bet_winner_id {
+            table.widow.folded_cards = code_cards(&self.folded_cards);
+        }
+        Ok(QafoonSnapshot {
+            table,
+            player_name: player.name.clone(),
+            player_team: self
+                .teams
+                .get(&player.team_id)
+                .map(|team: &Team| team.name.clone()),
+            player_cards: code_cards(&player.cards),
+        })
+    }
+
+    fn get_table_snapshot(&self) -> Result<TableSnapshot> {
+        let team_name = |team_id: TeamId| -> Option<String> {
+            self.teams
+                .get(&team_id)
+                .map(|team: &Team| team.name.clone())
+        };
+        let seat_ids: Vec<PlayerId> = if self.field.is_empty() {
+            self.players.keys().copied().sorted().collect()
+        } else {
+            self.get_field()
+        };
+        let seats: Vec<SeatSnapshot> = seat_ids
+            .into_iter()
+            .map(|seat_id: PlayerId| {
+                let seat: &Player = get_player!(self.players, seat_id)?;
+                Ok(SeatSnapshot {
+                    player_id: seat.id,
+                    name: seat.name.clone(),
+                    team: team_name(seat.team_id),
+                    cards_in_hand: seat.cards.len(),
+                    timeouts: seat.timeouts,
+                    is_bot: seat.is_bot,
+                    replaced_by_bot: seat.replaced_by_bot,
+                    kicked: seat.kicked,
+                })
+            })
+            .collect::<Result<Vec<SeatSnapshot>>>()?;
+        let teams: Vec<TeamSnapshot> = self
+            .teams
+            .values()
+            .sorted_by_key(ToString::to_string)
+            .map(|team: &Team| TeamSnapshot {
+                name: team.name.clone(),
+                players: team
+                    .players
+                    .iter()
+                    .filter_map(|id: &PlayerId| self.players.get(id))
+                    .map(|player: &Player| player.name.clone())
+                    .collect(),
+                game_score: team.score,
+                round_score: team.collected_hands.len(),
+            })
+            .collect();
+        let player_name_of = |id: PlayerId| -> Option<String> {
+            self.players
+                .get(&id)
+                .map(|player: &Player| player.name.clone())
+        };
+        let widow: WidowSnapshot = WidowSnapshot {
+            taken_by: player_name_of(self.bet_winner_id),
+            cards_to_fold: match self.phase {
+                GamePhase::Folding => self
+                    .players
+                    .get(&self.bet_winner_id)
+                    .map(|bettor: &Player| bettor.cards.len().saturating_sub(12))
+                    .unwrap_or_default(),
+                _ => 0,
+            },
+            folded_cards: Vec::new(),
+        };
+        Ok(TableSnapshot {
+            game_id: self.id,
+            game_status: self.status.clone(),
+            phase: self.phase.clone(),
+            rule_variant: self.get_rule_variant(),
+            seats,
+            teams,
+            starter: player_name_of(self.starter),
+            current_turn: self
+                .pending_demand
+                .as_ref()
+                .and_then(|pending: &PendingDemand| player_name_of(pending.player_id)),
+            current_hokm: self.hokm.clone(),
+            current_bet: self.bet.clone(),
+            ground_cards: self.get_ground_cards()?,
+            widow,
+        })
+    }
+
+    fn get_legal_cards(&self, player_id: PlayerId) -> Result<Vec<String>> {
+        let player: &Player = get_player!(self.players, player_id)?;
+        let has_matching_card: bool = !self.ground.cards.is_empty()
+            && player
+                .cards
+                .iter()
+                .any(|card: &Card| card.type_ == self.ground.type_);
+        Ok(player
+            .cards
+            .iter()
+            .filter(|card: &&Card| !has_matching_card || card.type_ == self.ground.type_)
+            .map(Card::code)
+            .collect())
+    }
+
     fn collect_hand(&mut self, player_to_collect_id: PlayerId) -> Result<()> {
         let team_to_collect_id: TeamId = get_player!(self.players, player_to_collect_id)?.team_id;
         let ground_cards: Vec<Card> = self.ground.cards.drain(..).map(|(_, card)| card).collect();
@@ -632,6 +978,7 @@ impl Qafoon {
         let mut highest_bet_option: Option<usize> = None;
         let mut highest_bettor_id: PlayerId = PlayerId::nil();
         let round_starter_index: usize = self.get_bettor_starter_index()?;
+        self.phase = GamePhase::Betting;
         loop {
             let mut bets: Vec<(String, PlayerChoice)> = Vec::new();
             for player_id in self
@@ -641,7 +988,8 @@ impl Qafoon {
                 .skip(round_starter_index)
                 .take(NUMBER_OF_PLAYERS)
             {
-                let mut message: GameMessage = GameMessage::demand(DemandMessage::Bet);
+                let mut message: GameMessage =
+                    GameMessage::demand_with_options(DemandMessage::Bet, get_bet_options());
                 let player_choice: Result<PlayerChoice> = self
                     .get_player_choice(player_id, &mut message, true, HIGHEST_BET)
                     .await;
@@ -690,6 +1038,7 @@ impl Qafoon {
                 })
                 .await?;
                 self.bet = (name, highest_bet);
+                self.bet_winner_id = highest_bettor_id;
                 self.update_shared_state().await?;
                 return Ok((highest_bet, highest_bettor_id, team_id));
             }
@@ -717,6 +1066,8 @@ impl Qafoon {
     }
 
     fn prepare_next_round(&mut self) -> Result<()> {
+        self.folded_cards.clear();
+        self.bet_winner_id = PlayerId::nil();
         self.teams.values_mut().for_each(|team: &mut Team| {
             team.collected_hands
                 .drain(..)
@@ -744,13 +1095,17 @@ impl Qafoon {
         for player_id in self.get_player_ids() {
             self.close_player_connection(player_id).await?;
         }
+        self.phase = GamePhase::Finished;
         self.set_status(GameStatus::Finished);
         Ok(())
     }
 
     async fn play_card(&mut self, player_id: PlayerId) -> Result<()> {
         let is_round_starter: bool = self.ground.cards.is_empty();
-        let mut message: GameMessage = GameMessage::demand(DemandMessage::PlayCard);
+        let mut message: GameMessage = GameMessage::demand_with_options(
+            DemandMessage::PlayCard,
+            self.get_legal_cards(player_id)?,
+        );
         loop {
             let player: &mut Player = get_player_mut!(self.players, player_id)?;
             let player_name: String = player.name.clone();
@@ -798,3 +1153,563 @@ impl Qafoon {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    fn seated_player(cards: &[&str]) -> (Qafoon, PlayerId) {
+        let mut game: Qafoon = Qafoon::new();
+        let player_id: PlayerId = PlayerId::new_v4();
+        let mut player: Player = Player::new("player".to_string(), player_id);
+        player.set_cards(
+            cards
+                .iter()
+                .map(|code: &&str| Card::try_from(code.to_string()).unwrap())
+                .collect(),
+        );
+        game.players.insert(player_id, player);
+        (game, player_id)
+    }
+
+    fn demand(demand: DemandMessage) -> GameMessage {
+        GameMessage::Demand {
+            demand,
+            error: String::new(),
+            options: Vec::new(),
+            remaining_time: None,
+        }
+    }
+
+    fn card(code: &str) -> PlayerChoice {
+        PlayerChoice::CardChoice(Card::try_from(code.to_string()).unwrap())
+    }
+
+    fn bot_table() -> Qafoon {
+        let mut game: Qafoon = Qafoon::new();
+        game.generate_teams().unwrap();
+        let team_ids: Vec<TeamId> = game.teams.keys().copied().sorted().collect();
+        for index in 0..NUMBER_OF_PLAYERS {
+            let player_id: PlayerId = PlayerId::new_v4();
+            let team_id: TeamId = team_ids[index % NUMBER_OF_TEAMS];
+            let mut player: Player = Player::new(format!("bot {index}"), player_id);
+            player.is_bot = true;
+            player.team_id = team_id;
+            game.teams
+                .get_mut(&team_id)
+                .unwrap()
+                .players
+                .push(player_id);
+            game.players.insert(player_id, player);
+        }
+        game.generate_field().unwrap();
+        game.set_status(GameStatus::Started);
+        game
+    }
+
+    /// Gives the seat a connection that accepts every message and hands them to the test.
+    fn connect(game: &mut Qafoon, player_id: PlayerId) -> UnboundedReceiver<GameMessage> {
+        crate::config::settings::init_test_config();
+        let (sender, mut receiver) = tokio::sync::mpsc::channel::<CorrelatedMessage>(16);
+        let (delivered_tx, delivered_rx) = tokio::sync::mpsc::unbounded_channel();
+        game.players_sender.insert(player_id, sender);
+        tokio::spawn(async move {
+            while let Some(CorrelatedMessage {
+                message,
+                response_tx,
+            }) = receiver.recv().await
+            {
+                let _ = delivered_tx.send(message);
+                let _ = response_tx.send(Ok(()));
+            }
+        });
+        delivered_rx
+    }
+
+    fn delivered(receiver: &mut UnboundedReceiver<GameMessage>) -> Vec<GameMessage> {
+        let mut messages: Vec<GameMessage> = Vec::new();
+        while let Ok(GameMessage::Sequenced { message, .. }) = receiver.try_recv() {
+            messages.push(*message);
+        }
+        messages
+    }
+
+    fn scores(game: &Qafoon) -> HashMap<TeamId, usize> {
+        game.teams
+            .values()
+            .map(|team: &Team| (team.id, team.score))
+            .collect()
+    }
+
+    fn restored(game: &Qafoon) -> Qafoon {
+        let mut restored: Qafoon = Qafoon::new();
+        restored
+            .restore_persisted_state(game.get_persisted_state().unwrap())
+            .unwrap();
+        restored
+    }
+
+    #[test]
+    fn auto_bet_passes() {
+        let (game, player_id) = seated_player(&["S-A", "H-2"]);
+        assert_eq!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::Bet))
+                .unwrap(),
+            PlayerChoice::Pass
+        );
+    }
+
+    #[test]
+    fn auto_hokm_picks_the_longest_suit() {
+        let (game, player_id) = seated_player(&["S-A", "D-2", "D-5", "D-K", "C-3", "C-4"]);
+        assert_eq!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::Hokm))
+                .unwrap(),
+            PlayerChoice::HokmChoice(Hokm::Diamonds)
+        );
+    }
+
+    #[test]
+    fn auto_fold_discards_the_lowest_card() {
+        let (game, player_id) = seated_player(&["S-A", "H-4", "C-3", "D-K"]);
+        assert_eq!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::Fold))
+                .unwrap(),
+            card("C-3")
+        );
+    }
+
+    #[test]
+    fn auto_play_follows_the_ground_suit() {
+        let (mut game, player_id) = seated_player(&["S-2", "H-9", "H-Q", "C-3"]);
+        game.ground.type_ = Hokm::Hearts;
+        game.ground.cards.push((
+            PlayerId::new_v4(),
+            Card::try_from("H-K".to_string()).unwrap(),
+        ));
+        assert_eq!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::PlayCard))
+                .unwrap(),
+            card("H-9")
+        );
+    }
+
+    #[test]
+    fn auto_play_leads_with_the_lowest_card() {
+        let (game, player_id) = seated_player(&["S-5", "H-9", "C-3"]);
+        assert_eq!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::PlayCard))
+                .unwrap(),
+            card("C-3")
+        );
+    }
+
+    #[test]
+    fn auto_choice_needs_a_card_to_play() {
+        let (game, player_id) = seated_player(&[]);
+        assert!(matches!(
+            game.get_auto_choice(player_id, &demand(DemandMessage::PlayCard)),
+            Err(Error::NoValidCard)
+        ));
+        assert!(game
+            .get_auto_choice(PlayerId::new_v4(), &demand(DemandMessage::Bet))
+            .is_err());
+    }
+
+    #[test]
+    fn removing_a_seat_drops_the_player_state() {
+        let (mut game, player_id) = seated_player(&["S-A"]);
+        let (other_game, other_id) = seated_player(&["H-2"]);
+        game.players.extend(other_game.players);
+        for id in [player_id, other_id] {
+            game.replay_buffer
+                .logs
+                .insert(id, PlayerMessageLog::default());
+            game.time_banks
+                .insert(id, std::time::Duration::from_secs(60));
+        }
+        let removed: Option<Player> = game.remove_player_seat(player_id);
+        assert_eq!(removed.map(|player: Player| player.id), Some(player_id));
+        assert_eq!(game.get_player_count(), 1);
+        assert!(!game.replay_buffer.logs.contains_key(&player_id));
+        assert!(!game.time_banks.contains_key(&player_id));
+        assert!(game.replay_buffer.logs.contains_key(&other_id));
+        assert!(game.time_banks.contains_key(&other_id));
+        assert!(game.remove_player_seat(player_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn resuming_after_the_last_trick_scores_the_round_once() {
+        let mut game: Qafoon = bot_table();
+        let bettor_id: PlayerId = game.field[0];
+        let off_team_id: TeamId = game.players[&bettor_id].team_id;
+        let def_team_id: TeamId = game.get_opposing_team_id(off_team_id).unwrap();
+        game.phase = GamePhase::Playing;
+        game.bet = (game.players[&bettor_id].name.clone(), 7);
+        game.bet_winner_id = bettor_id;
+        game.trick_starter = bettor_id;
+        let off_team: &mut Team = game.teams.get_mut(&off_team_id).unwrap();
+        off_team.score = 100;
+        off_team.collected_hands = vec![Vec::new(); 7];
+        let def_team: &mut Team = game.teams.get_mut(&def_team_id).unwrap();
+        def_team.score = 30;
+        def_team.collected_hands = vec![Vec::new(); 6];
+
+        let mut restored: Qafoon = restored(&game);
+        assert_eq!(scores(&restored), scores(&game));
+        assert!(restored.resumes_mid_round());
+        restored.run_game().await.unwrap();
+        assert_eq!(restored.teams[&off_team_id].score, 107);
+        assert_eq!(restored.teams[&def_team_id].score, 30);
+        assert_eq!(restored.get_status(), &GameStatus::Finished);
+    }
+
+    #[test]
+    fn round_start_snapshots_replay_the_round() {
+        let mut game: Qafoon = bot_table();
+        game.phase = GamePhase::Shuffling;
+        for (score, team) in game.teams.values_mut().enumerate() {
+            team.score = 40 + score;
+        }
+        let restored: Qafoon = restored(&game);
+        assert_eq!(scores(&restored), scores(&game));
+        assert_eq!(restored.get_status(), &GameStatus::Started);
+        assert!(!restored.resumes_mid_round());
+        assert!(restored
+            .teams
+            .values()
+            .all(|team: &Team| team.collected_hands.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn suspending_before_the_first_round_ends_the_game() {
+        let mut game: Qafoon = bot_table();
+        game.set_status(GameStatus::NotStarted);
+        assert!(game.suspend_game("Maintenance".to_string()).await.is_err());
+        assert_eq!(game.get_status(), &GameStatus::Ended);
+
+        let mut game: Qafoon = bot_table();
+        assert!(game.suspend_game("Maintenance".to_string()).await.is_err());
+        assert_eq!(game.get_status(), &GameStatus::Suspended);
+    }
+
+    #[test]
+    fn player_snapshots_only_show_their_own_hand() {
+        let mut game: Qafoon = bot_table();
+        let (bettor_id, other_id): (PlayerId, PlayerId) = (game.field[0], game.field[1]);
+        let hand = |codes: &[&str]| -> Vec<Card> {
+            codes
+                .iter()
+                .map(|code: &&str| Card::try_from(code.to_string()).unwrap())
+                .collect()
+        };
+        game.players
+            .get_mut(&bettor_id)
+            .unwrap()
+            .set_cards(hand(&["S-A", "H-2", "C-3"]));
+        game.players
+            .get_mut(&other_id)
+            .unwrap()
+            .set_cards(hand(&["D-5"]));
+        game.bet_winner_id = bettor_id;
+        game.folded_cards = hand(&["H-K", "D-Q"]);
+
+        let snapshot: QafoonSnapshot = game.get_player_snapshot(other_id).unwrap();
+        assert_eq!(snapshot.player_name, game.players[&other_id].name);
+        assert_eq!(snapshot.player_cards, vec!["D-5".to_string()]);
+        assert!(snapshot.table.widow.folded_cards.is_empty());
+        assert_eq!(
+            snapshot.table.widow.taken_by,
+            Some(game.players[&bettor_id].name.clone())
+        );
+        assert_eq!(
+            snapshot
+                .table
+                .seats
+                .iter()
+                .map(|seat: &SeatSnapshot| (seat.player_id, seat.cards_in_hand))
+                .collect::<Vec<(PlayerId, usize)>>(),
+            game.field
+                .iter()
+                .map(|id: &PlayerId| (*id, game.players[id].cards.len()))
+                .collect::<Vec<(PlayerId, usize)>>()
+        );
+
+        let snapshot: QafoonSnapshot = game.get_player_snapshot(bettor_id).unwrap();
+        assert_eq!(snapshot.player_cards.len(), 3);
+        assert_eq!(
+            snapshot.table.widow.folded_cards,
+            vec!["H-K".to_string(), "D-Q".to_string()]
+        );
+        assert!(game.get_player_snapshot(PlayerId::new_v4()).is_err());
+    }
+
+    #[tokio::test]
+    async fn turns_are_announced_to_every_connected_seat() {
+        let mut game: Qafoon = bot_table();
+        let mut receivers: Vec<UnboundedReceiver<GameMessage>> = game
+            .field
+            .clone()
+            .into_iter()
+            .map(|player_id: PlayerId| connect(&mut game, player_id))
+            .collect();
+        let deadline: tokio::time::Instant =
+            tokio::time::Instant::now() + std::time::Duration::from_secs(20);
+
+        game.announce_turn("bot 0".to_string(), "Bet".to_string(), Some(deadline))
+            .await
+            .unwrap();
+        for receiver in receivers.iter_mut() {
+            let messages: Vec<GameMessage> = delivered(receiver);
+            let [GameMessage::Broadcast {
+                message: BroadcastMessage::PlayerTurn { turn },
+            }] = messages.as_slice()
+            else {
+                panic!("expected a single turn announcement, got {messages:?}");
+            };
+            assert_eq!(turn.name, "bot 0");
+            assert_eq!(turn.action, "Bet");
+            let expected: u64 = crate::core::extensions::deadline_timestamp(deadline);
+            assert!(turn
+                .deadline
+                .is_some_and(|at: u64| at.abs_diff(expected) < 1_000));
+            assert!(turn
+                .remaining_time
+                .is_some_and(|remaining: u64| (19_000..=20_000).contains(&remaining)));
+        }
+
+        game.announce_turn("bot 1".to_string(), "Play".to_string(), None)
+            .await
+            .unwrap();
+        let messages: Vec<GameMessage> = delivered(&mut receivers[0]);
+        assert_eq!(
+            messages,
+            vec![GameMessage::Broadcast {
+                message: BroadcastMessage::PlayerTurn {
+                    turn: TurnInfo {
+                        name: "bot 1".to_string(),
+                        action: "Play".to_string(),
+                        deadline: None,
+                        remaining_time: None,
+                    },
+                },
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn bots_keep_a_seat_until_its_player_reclaims_it() {
+        let mut game: Qafoon = bot_table();
+        let (player_id, partner_id): (PlayerId, PlayerId) = (game.field[0], game.field[2]);
+        game.players.get_mut(&player_id).unwrap().is_bot = false;
+        game.players.get_mut(&partner_id).unwrap().is_bot = false;
+        game.players
+            .get_mut(&player_id)
+            .unwrap()
+            .consecutive_timeouts = 3;
+        let mut partner: UnboundedReceiver<GameMessage> = connect(&mut game, partner_id);
+
+        game.hand_seat_to_bot(player_id).await.unwrap();
+        assert!(game.players[&player_id].is_bot);
+        assert!(game.players[&player_id].replaced_by_bot);
+        assert!(!game.players_sender.contains_key(&player_id));
+        assert_eq!(game.get_status(), &GameStatus::Started);
+        assert!(delivered(&mut partner).contains(&GameMessage::Broadcast {
+            message: BroadcastMessage::BotTakeover {
+                name: game.players[&player_id].name.clone(),
+            },
+        }));
+
+        let mut player: UnboundedReceiver<GameMessage> = connect(&mut game, player_id);
+        game.reclaim_seat(player_id).await.unwrap();
+        assert!(!game.players[&player_id].is_bot);
+        assert_eq!(game.players[&player_id].consecutive_timeouts, 0);
+        assert!(game.players[&player_id].replaced_by_bot);
+        let messages: Vec<GameMessage> = delivered(&mut player);
+        assert!(messages.contains(&GameMessage::Broadcast {
+            message: BroadcastMessage::SeatReclaimed {
+                name: game.players[&player_id].name.clone(),
+            },
+        }));
+        assert!(messages
+            .iter()
+            .any(|message: &GameMessage| matches!(message, GameMessage::FullState { .. })));
+    }
+
+    #[tokio::test]
+    async fn pending_demands_are_reissued_with_the_time_left() {
+        let mut game: Qafoon = bot_table();
+        let (player_id, other_id): (PlayerId, PlayerId) = (game.field[0], game.field[1]);
+        game.set_pending_demand(Some(PendingDemand {
+            player_id,
+            message: GameMessage::team(vec!["Team 1".to_string()], String::new()),
+            deadline: Some(tokio::time::Instant::now() + std::time::Duration::from_secs(10)),
+        }));
+        let mut player: UnboundedReceiver<GameMessage> = connect(&mut game, player_id);
+        let mut other: UnboundedReceiver<GameMessage> = connect(&mut game, other_id);
+
+        game.reissue_pending_demand(other_id).await.unwrap();
+        assert!(delivered(&mut other).is_empty());
+
+        game.reissue_pending_demand(player_id).await.unwrap();
+        let messages: Vec<GameMessage> = delivered(&mut player);
+        assert_eq!(messages.len(), 1);
+        let GameMessage::Demand {
+            demand: DemandMessage::Team { available_teams },
+            remaining_time: Some(remaining_time),
+            ..
+        } = &messages[0]
+        else {
+            panic!("expected a team demand, got {:?}", messages[0]);
+        };
+        assert_eq!(available_teams, &vec!["Team 1".to_string()]);
+        assert!((9_000..=10_000).contains(remaining_time));
+    }
+
+    #[tokio::test]
+    async fn disconnects_are_announced_until_the_seat_is_settled() {
+        let mut game: Qafoon = bot_table();
+        let (player_id, partner_id): (PlayerId, PlayerId) = (game.field[0], game.field[2]);
+        game.players.get_mut(&player_id).unwrap().is_bot = false;
+        game.players.get_mut(&partner_id).unwrap().is_bot = false;
+        let mut partner: UnboundedReceiver<GameMessage> = connect(&mut game, partner_id);
+        let (commands, command_receiver) = tokio::sync::mpsc::channel::<GameCommand>(4);
+        game.command_receiver = Some(command_receiver);
+        commands
+            .send(GameCommand::Kick {
+                player_id,
+                reason: "Idle".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let player_name: String = game.players[&player_id].name.clone();
+        let mut players_to_reconnect: Vec<(PlayerId, String)> =
+            vec![(player_id, player_name.clone())];
+        game.handle_player_reconnection(&mut players_to_reconnect)
+            .await
+            .unwrap();
+        assert!(players_to_reconnect.is_empty());
+        assert!(game.players[&player_id].kicked);
+        assert_eq!(game.get_status(), &GameStatus::Started);
+
+        let messages: Vec<GameMessage> = delivered(&mut partner);
+        let Some(GameMessage::Broadcast {
+            message: BroadcastMessage::PlayerDisconnected { info },
+        }) = messages.first()
+        else {
+            panic!("expected a disconnect announcement, got {messages:?}");
+        };
+        assert_eq!(info.name, player_name);
+        assert!(info.remaining_time <= get_config().timeout.player_reconnection.as_millis() as u64);
+        assert_eq!(
+            messages.get(1),
+            Some(&GameMessage::Broadcast {
+                message: BroadcastMessage::BotTakeover { name: player_name },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn taking_over_a_seat_tells_the_previous_device() {
+        let mut game: Qafoon = bot_table();
+        let player_id: PlayerId = game.field[0];
+        game.players.get_mut(&player_id).unwrap().is_bot = false;
+        let mut previous: UnboundedReceiver<GameMessage> = connect(&mut game, player_id);
+
+        game.end_previous_session(player_id).await;
+        assert_eq!(previous.try_recv().ok(), Some(GameMessage::SessionMoved));
+        assert!(!game.players[&player_id].is_bot);
+        assert_eq!(game.get_status(), &GameStatus::Started);
+
+        game.close_player_connection(player_id).await.unwrap();
+        game.end_previous_session(player_id).await;
+        assert!(previous.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn admin_commands_are_answered_by_the_game() {
+        let mut game: Qafoon = bot_table();
+        let player_id: PlayerId = game.field[0];
+        game.players.get_mut(&player_id).unwrap().is_bot = false;
+        let mut player: UnboundedReceiver<GameMessage> = connect(&mut game, player_id);
+
+        let (respond_to, summary) = tokio::sync::oneshot::channel::<GameSummary>();
+        game.handle_command(GameCommand::Summary { respond_to })
+            .await
+            .unwrap();
+        let summary: GameSummary = summary.await.unwrap();
+        assert_eq!(summary.players.len(), NUMBER_OF_PLAYERS);
+        assert!(summary
+            .players
+            .iter()
+            .all(|seat: &SeatSummary| seat.connected == (seat.player_id == player_id)));
+
+        let (respond_to, snapshot) = tokio::sync::oneshot::channel::<Result<Value>>();
+        game.handle_command(GameCommand::Snapshot { respond_to })
+            .await
+            .unwrap();
+        assert_eq!(
+            snapshot.await.unwrap().unwrap()["seats"]
+                .as_array()
+                .map(Vec::len),
+            Some(NUMBER_OF_PLAYERS)
+        );
+
+        let (respond_to, state) = tokio::sync::oneshot::channel::<Result<Value>>();
+        game.handle_command(GameCommand::Inspect { respond_to })
+            .await
+            .unwrap();
+        let mut inspected: Qafoon = Qafoon::new();
+        inspected
+            .restore_persisted_state(state.await.unwrap().unwrap())
+            .unwrap();
+        assert_eq!(inspected.field, game.field);
+
+        let message: GameMessage = GameMessage::Broadcast {
+            message: BroadcastMessage::BotTakeover {
+                name: "bot 1".to_string(),
+            },
+        };
+        game.replay_buffer.record(player_id, message.clone());
+        let (respond_to, history) = tokio::sync::oneshot::channel::<Vec<GameMessage>>();
+        game.handle_command(GameCommand::History {
+            player_id,
+            respond_to,
+        })
+        .await
+        .unwrap();
+        assert_eq!(history.await.unwrap().len(), 1);
+
+        game.handle_command(GameCommand::Tell {
+            player_id,
+            message: message.clone(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(player.try_recv().ok(), Some(message));
+
+        assert!(game
+            .handle_command(GameCommand::Shutdown {
+                reason: "Maintenance".to_string(),
+            })
+            .await
+            .is_err());
+        assert_eq!(game.get_status(), &GameStatus::Ended);
+        assert!(delivered(&mut player).contains(&GameMessage::Broadcast {
+            message: BroadcastMessage::GameCancelled {
+                reason: "Maintenance".to_string(),
+            },
+        }));
+    }
+
+    #[tokio::test]
+    async fn handing_the_last_seat_to_a_bot_ends_the_game() {
+        let mut game: Qafoon = bot_table();
+        let player_id: PlayerId = game.field[1];
+        game.players.get_mut(&player_id).unwrap().is_bot = false;
+        assert!(game.hand_seat_to_bot(player_id).await.is_err());
+        assert_eq!(game.get_status(), &GameStatus::Ended);
+    }
+}
@@ -12,6 +12,78 @@ pub struct GameSharedState {
     pub game_status: GameStatus,
 }
 
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct SeatSnapshot {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub team: Option<String>,
+    pub cards_in_hand: usize,
+    pub timeouts: usize,
+    pub is_bot: bool,
+    pub replaced_by_bot: bool,
+    pub kicked: bool,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct TeamSnapshot {
+    pub name: String,
+    pub players: Vec<String>,
+    pub game_score: usize,
+    pub round_score: usize,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct WidowSnapshot {
+    pub taken_by: Option<String>,
+    pub cards_to_fold: usize,
+    pub folded_cards: Vec<String>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct TableSnapshot {
+    pub game_id: GameId,
+    pub game_status: GameStatus,
+    pub phase: GamePhase,
+    pub rule_variant: String,
+    pub seats: Vec<SeatSnapshot>,
+    pub teams: Vec<TeamSnapshot>,
+    pub starter: Option<String>,
+    pub current_turn: Option<String>,
+    pub current_hokm: Hokm,
+    pub current_bet: (String, usize),
+    pub ground_cards: Vec<(String, String)>,
+    pub widow: WidowSnapshot,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct QafoonSnapshot {
+    #[serde(flatten)]
+    pub table: TableSnapshot,
+    pub player_name: String,
+    pub player_team: Option<String>,
+    pub player_cards: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct QafoonState {
+    pub id: GameId,
+    pub teams: HashMap<TeamId, Team>,
+    pub players: HashMap<PlayerId, Player>,
+    pub field: Vec<PlayerId>,
+    pub starter: PlayerId,
+    pub trick_starter: PlayerId,
+    pub hokm: Hokm,
+    pub bet: (String, usize),
+    pub bet_winner_id: PlayerId,
+    pub folded_cards: Vec<Card>,
+    pub ground: Ground,
+    pub deck: Vec<Card>,
+    pub rng_seed: u64,
+    pub time_banks: HashMap<PlayerId, std::time::Duration>,
+    pub status: GameStatus,
+    pub phase: GamePhase,
+}
+
 #[derive(Default)]
 pub struct Qafoon {
     pub id: GameId,
@@ -20,13 +92,21 @@ pub struct Qafoon {
     pub players_receiver: HashMap<PlayerId, Receiver<Result<GameMessage>>>,
     pub players_sender: HashMap<PlayerId, Sender<CorrelatedMessage>>,
     pub player_connections: HashMap<PlayerId, PlayerConnection>,
-    pub players_reconnection_receiver: Option<Receiver<(PlayerId, Stream)>>,
+    pub command_receiver: Option<Receiver<GameCommand>>,
+    pub replay_buffer: ReplayBuffer,
+    pub pending_demand: Option<PendingDemand>,
+    pub time_banks: HashMap<PlayerId, std::time::Duration>,
     pub shared_state: Arc<tokio::sync::RwLock<GameSharedState>>,
     pub field: Vec<PlayerId>,
     pub cards: Vec<Card>,
     pub starter: PlayerId,
+    pub trick_starter: PlayerId,
     pub hokm: Hokm,
     pub bet: (String, usize),
     pub ground: Ground,
     pub status: GameStatus,
+    pub phase: GamePhase,
+    pub bet_winner_id: PlayerId,
+    pub folded_cards: Vec<Card>,
+    pub rng_seed: u64,
 }
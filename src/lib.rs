@@ -0,0 +1,11 @@
+pub mod api;
+pub mod auth;
+pub mod config;
+pub mod core;
+pub mod database;
+pub mod errors;
+pub mod games;
+pub mod macros;
+pub mod models;
+pub mod network;
+pub mod prelude;
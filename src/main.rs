@@ -1,14 +1,4 @@
-mod api;
-mod auth;
-mod config;
-mod core;
-mod database;
-mod errors;
-mod games;
-mod macros;
-mod models;
-mod network;
-mod prelude;
+use cards::{api, config, core, database, network};
 
 #[tokio::main]
 async fn main() -> core::types::Result<()> {
@@ -19,16 +9,36 @@ async fn main() -> core::types::Result<()> {
     database::test_database_connection(&pool).await?;
     database::run_migrations(&pool).await?;
     println!("Starting servers...");
+    let (api_shutdown_tx, api_shutdown_rx) = tokio::sync::oneshot::channel();
+    let (game_shutdown_tx, game_shutdown_rx) = tokio::sync::oneshot::channel();
+    let user_repo: database::UserRepository = database::UserRepository::new(pool.clone());
+    let mut api_server: tokio::task::JoinHandle<()> =
+        api::init_api_server(pool, api_shutdown_rx).await?;
+    let mut game_server: tokio::task::JoinHandle<()> =
+        network::init_game_server(user_repo, game_shutdown_rx).await?;
     tokio::select! {
-        result = api::init_api_server(pool).await? => {
+        result = &mut api_server => {
             eprintln!("API server exited unexpectedly: {result:?}");
         }
-        result = network::init_game_server().await? => {
+        result = &mut game_server => {
             eprintln!("Game server exited unexpectedly: {result:?}");
         }
         _ = tokio::signal::ctrl_c() => {
-            println!("Shutdown signal received, stopping servers...");
+            println!("Shutdown signal received, draining servers...");
         }
     }
+    // The game server keeps accepting during the drain so players of running games can
+    // reconnect; the registry turns away new queue joins.
+    core::get_game_registry()
+        .drain(config::get_config().timeout.shutdown_drain)
+        .await;
+    let _ = api_shutdown_tx.send(());
+    let _ = game_shutdown_tx.send(());
+    for server in [api_server, game_server] {
+        if !server.is_finished() {
+            let _ = server.await;
+        }
+    }
+    println!("Servers stopped");
     Ok(())
 }
@@ -0,0 +1,40 @@
+use tokio::sync::oneshot;
+
+use crate::{
+    models::{GameSummary, ReconnectionRequest},
+    prelude::*,
+};
+
+pub enum GameCommand {
+    Reconnect(Box<ReconnectionRequest>),
+    Kick {
+        player_id: PlayerId,
+        reason: String,
+    },
+    Snapshot {
+        respond_to: oneshot::Sender<Result<Value>>,
+    },
+    Summary {
+        respond_to: oneshot::Sender<GameSummary>,
+    },
+    Inspect {
+        respond_to: oneshot::Sender<Result<Value>>,
+    },
+    History {
+        player_id: PlayerId,
+        respond_to: oneshot::Sender<Vec<GameMessage>>,
+    },
+    Shutdown {
+        reason: String,
+    },
+    Suspend {
+        reason: String,
+    },
+    Notify {
+        message: BroadcastMessage,
+    },
+    Tell {
+        player_id: PlayerId,
+        message: GameMessage,
+    },
+}
@@ -93,6 +93,21 @@ pub enum GameStatus {
     Started,
     Finished,
     Ended,
+    Suspended,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum GamePhase {
+    #[default]
+    Waiting,
+    TeamSelection,
+    Shuffling,
+    Dealing,
+    Betting,
+    Folding,
+    ChoosingHokm,
+    Playing,
+    Finished,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -105,6 +120,8 @@ pub enum GameMessage {
     Demand {
         demand: DemandMessage,
         error: String,
+        options: Vec<String>,
+        remaining_time: Option<u64>,
     },
     Cards {
         player_cards: Vec<String>,
@@ -139,6 +156,21 @@ pub enum GameMessage {
     FullState {
         state: Value,
     },
+    Reconnect {
+        token: String,
+        last_seq: u64,
+    },
+    Sequenced {
+        seq: u64,
+        message: Box<GameMessage>,
+    },
+    SessionMoved,
+    Kicked {
+        reason: String,
+    },
+    Banned {
+        message: String,
+    },
 }
 
 impl GameMessage {
@@ -159,6 +191,11 @@ impl GameMessage {
             GameMessage::AlreadyInQueueError { .. } => "AlreadyInQueueError".to_string(),
             GameMessage::AlreadyInGameError { .. } => "AlreadyInGameError".to_string(),
             GameMessage::FullState { .. } => "FullState".to_string(),
+            GameMessage::Reconnect { .. } => "Reconnect".to_string(),
+            GameMessage::Sequenced { message, .. } => message.message_type(),
+            GameMessage::SessionMoved => "SessionMoved".to_string(),
+            GameMessage::Kicked { .. } => "Kicked".to_string(),
+            GameMessage::Banned { .. } => "Banned".to_string(),
         }
     }
     pub fn set_demand_error(&mut self, new_error: String) {
@@ -167,16 +204,33 @@ impl GameMessage {
         };
         *error = new_error;
     }
+    pub fn set_demand_options(&mut self, new_options: Vec<String>) {
+        if let GameMessage::Demand { options, .. } = self {
+            *options = new_options;
+        }
+    }
+    pub fn set_demand_remaining_time(&mut self, new_remaining_time: Option<u64>) {
+        if let GameMessage::Demand { remaining_time, .. } = self {
+            *remaining_time = new_remaining_time;
+        }
+    }
     pub fn team(available_teams: Vec<String>, error: String) -> Self {
         GameMessage::Demand {
             demand: DemandMessage::Team { available_teams },
             error,
+            options: Vec::new(),
+            remaining_time: None,
         }
     }
     pub fn demand(demand: DemandMessage) -> Self {
+        GameMessage::demand_with_options(demand, Vec::new())
+    }
+    pub fn demand_with_options(demand: DemandMessage, options: Vec<String>) -> Self {
         GameMessage::Demand {
             demand,
             error: String::new(),
+            options,
+            remaining_time: None,
         }
     }
 }
@@ -210,9 +264,13 @@ pub enum BroadcastMessage {
     GameError { error: String },
     GameTimeout,
     QueueTimeout,
+    PlayerLeft { name: String },
+    QueueStatus { status: crate::models::QueueStatus },
     TeamSelectionStarting,
     EmptyGround,
     GameCancelled { reason: String },
+    GameSuspended { reason: String },
+    ServerMaintenance { deadline: u64 },
     HandingOutCards,
     ShufflingCards,
     Starter { name: String },
@@ -223,6 +281,13 @@ pub enum BroadcastMessage {
     RoundWinner { round_winner: String },
     GameWinner { game_winner: String },
     GameScore { teams_score: Vec<(String, usize)> },
+    PlayerTurn { turn: crate::models::TurnInfo },
+    TimePenalty { name: String, penalty: String },
+    PlayerTimedOut { name: String, timeouts: usize },
+    BotTakeover { name: String },
+    SeatReclaimed { name: String },
+    PlayerDisconnected { info: crate::models::DisconnectInfo },
+    PlayerReconnected { name: String },
     RoundScore { teams_score: Vec<(String, usize)> },
 }
 
@@ -1,5 +1,6 @@
 use crate::{models::Card, prelude::*};
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Ground {
     pub cards: Vec<(PlayerId, Card)>,
     pub type_: Hokm,
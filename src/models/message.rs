@@ -3,3 +3,57 @@ pub struct CorrelatedMessage {
     pub message: super::GameMessage,
     pub response_tx: tokio::sync::oneshot::Sender<crate::core::Result<()>>,
 }
+
+pub struct ReconnectionRequest {
+    pub player_id: crate::core::PlayerId,
+    pub connection: crate::core::Stream,
+    pub last_seq: Option<u64>,
+    pub takeover: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingDemand {
+    pub player_id: crate::core::PlayerId,
+    pub message: super::GameMessage,
+    pub deadline: Option<tokio::time::Instant>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct TurnInfo {
+    pub name: String,
+    pub action: String,
+    pub deadline: Option<u64>,
+    pub remaining_time: Option<u64>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct QueueStatus {
+    pub players_present: usize,
+    pub seats_needed: usize,
+    pub cutoff_in: u64,
+    pub estimated_wait: Option<u64>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct DisconnectInfo {
+    pub name: String,
+    pub deadline: u64,
+    pub remaining_time: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct SeatSummary {
+    pub player_id: crate::core::PlayerId,
+    pub name: String,
+    pub team: Option<String>,
+    pub is_bot: bool,
+    pub connected: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct GameSummary {
+    pub status: super::GameStatus,
+    pub phase: super::GamePhase,
+    pub players: Vec<SeatSummary>,
+    pub scores: Vec<(String, usize)>,
+}
@@ -1,15 +1,19 @@
 pub mod card;
+pub mod command;
 pub mod enums;
 pub mod ground;
 pub mod message;
 pub mod player;
+pub mod replay;
 pub mod session;
 pub mod team;
 
 pub use card::*;
+pub use command::*;
 pub use enums::*;
 pub use ground::*;
 pub use message::*;
 pub use player::*;
+pub use replay::*;
 pub use session::*;
 pub use team::*;
@@ -2,11 +2,19 @@ use tokio::{sync::oneshot::Sender, task::JoinHandle};
 
 use crate::{models::Card, prelude::*};
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Player {
     pub id: PlayerId,
     pub name: String,
     pub team_id: TeamId,
     pub cards: Vec<Card>,
+    pub timeouts: usize,
+    pub consecutive_timeouts: usize,
+    pub is_bot: bool,
+    pub replaced_by_bot: bool,
+    /// Kicked players keep their seat for the bot but may not reclaim it.
+    #[serde(default)]
+    pub kicked: bool,
 }
 
 impl Player {
@@ -16,6 +24,11 @@ impl Player {
             name,
             team_id: TeamId::nil(),
             cards: Vec::new(),
+            timeouts: 0,
+            consecutive_timeouts: 0,
+            is_bot: false,
+            replaced_by_bot: false,
+            kicked: false,
         }
     }
 
@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+#[derive(Default)]
+pub struct PlayerMessageLog {
+    pub last_seq: u64,
+    pub resynced_seq: u64,
+    pub messages: VecDeque<(u64, GameMessage)>,
+}
+
+#[derive(Default)]
+pub struct ReplayBuffer {
+    pub logs: HashMap<PlayerId, PlayerMessageLog>,
+}
+
+impl ReplayBuffer {
+    pub fn record(&mut self, player_id: PlayerId, message: GameMessage) -> GameMessage {
+        let capacity: usize = get_config().game_server.replay_buffer_size;
+        self.record_within(player_id, message, capacity)
+    }
+
+    fn record_within(
+        &mut self,
+        player_id: PlayerId,
+        message: GameMessage,
+        capacity: usize,
+    ) -> GameMessage {
+        let log: &mut PlayerMessageLog = self.logs.entry(player_id).or_default();
+        log.last_seq += 1;
+        let sequenced: GameMessage = GameMessage::Sequenced {
+            seq: log.last_seq,
+            message: Box::new(message),
+        };
+        log.messages.push_back((log.last_seq, sequenced.clone()));
+        while log.messages.len() > capacity {
+            log.messages.pop_front();
+        }
+        sequenced
+    }
+
+    pub fn missed_messages(&self, player_id: PlayerId, last_seen: u64) -> Option<Vec<GameMessage>> {
+        let Some(log) = self.logs.get(&player_id) else {
+            return (last_seen == 0).then(Vec::new);
+        };
+        if last_seen > log.last_seq {
+            return None;
+        }
+        let oldest_seq: u64 = log
+            .messages
+            .front()
+            .map(|(seq, _)| *seq)
+            .unwrap_or(log.last_seq + 1);
+        if last_seen + 1 < oldest_seq {
+            return None;
+        }
+        Some(
+            log.messages
+                .iter()
+                .filter(|(seq, _)| *seq > last_seen)
+                .map(|(_, message)| message.clone())
+                .collect(),
+        )
+    }
+
+    pub fn last_seq(&self, player_id: PlayerId) -> u64 {
+        self.logs
+            .get(&player_id)
+            .map_or(0, |log: &PlayerMessageLog| log.last_seq)
+    }
+
+    /// Notes that a resync brought the player up to date with everything recorded so far.
+    pub fn mark_resynced(&mut self, player_id: PlayerId) {
+        if let Some(log) = self.logs.get_mut(&player_id) {
+            log.resynced_seq = log.last_seq;
+        }
+    }
+
+    /// Whether a resync already delivered the message recorded as `seq`, or a newer state.
+    pub fn was_resynced(&self, player_id: PlayerId, seq: u64) -> bool {
+        self.logs
+            .get(&player_id)
+            .is_some_and(|log: &PlayerMessageLog| log.resynced_seq >= seq)
+    }
+
+    /// Everything still buffered for the player, oldest first.
+    pub fn history(&self, player_id: PlayerId) -> Vec<GameMessage> {
+        self.logs
+            .get(&player_id)
+            .map(|log: &PlayerMessageLog| {
+                log.messages
+                    .iter()
+                    .map(|(_, message)| message.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.logs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(count: usize) -> GameMessage {
+        GameMessage::Cards {
+            player_cards: vec!["A♠".to_string(); count],
+        }
+    }
+
+    fn seq_of(message: &GameMessage) -> u64 {
+        match message {
+            GameMessage::Sequenced { seq, .. } => *seq,
+            other => panic!("Expected a sequenced message, got {}", other.message_type()),
+        }
+    }
+
+    #[test]
+    fn sequences_are_counted_per_player() {
+        let mut buffer: ReplayBuffer = ReplayBuffer::default();
+        let (first, second): (PlayerId, PlayerId) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        assert_eq!(seq_of(&buffer.record_within(first, cards(1), 8)), 1);
+        assert_eq!(seq_of(&buffer.record_within(first, cards(2), 8)), 2);
+        assert_eq!(seq_of(&buffer.record_within(second, cards(1), 8)), 1);
+        assert_eq!(
+            buffer.record_within(first, cards(3), 8),
+            GameMessage::Sequenced {
+                seq: 3,
+                message: Box::new(cards(3)),
+            }
+        );
+    }
+
+    #[test]
+    fn missed_messages_are_replayed_after_the_last_seen() {
+        let mut buffer: ReplayBuffer = ReplayBuffer::default();
+        let player_id: PlayerId = uuid::Uuid::new_v4();
+        for count in 1..=4 {
+            buffer.record_within(player_id, cards(count), 8);
+        }
+        let missed: Vec<u64> = buffer
+            .missed_messages(player_id, 2)
+            .expect("Messages after 2 are buffered")
+            .iter()
+            .map(seq_of)
+            .collect();
+        assert_eq!(missed, vec![3, 4]);
+        assert_eq!(buffer.missed_messages(player_id, 4), Some(Vec::new()));
+        assert_eq!(buffer.missed_messages(player_id, 5), None);
+    }
+
+    #[test]
+    fn evicted_messages_cannot_be_replayed() {
+        let mut buffer: ReplayBuffer = ReplayBuffer::default();
+        let player_id: PlayerId = uuid::Uuid::new_v4();
+        for count in 1..=5 {
+            buffer.record_within(player_id, cards(count), 3);
+        }
+        assert_eq!(buffer.missed_messages(player_id, 1), None);
+        assert_eq!(
+            buffer
+                .missed_messages(player_id, 2)
+                .map(|messages: Vec<GameMessage>| messages.len()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn unknown_players_only_resume_from_the_start() {
+        let buffer: ReplayBuffer = ReplayBuffer::default();
+        let player_id: PlayerId = uuid::Uuid::new_v4();
+        assert_eq!(buffer.missed_messages(player_id, 0), Some(Vec::new()));
+        assert_eq!(buffer.missed_messages(player_id, 1), None);
+    }
+
+    #[test]
+    fn resyncs_cover_the_messages_recorded_before_them() {
+        let mut buffer: ReplayBuffer = ReplayBuffer::default();
+        let player_id: PlayerId = uuid::Uuid::new_v4();
+        buffer.record_within(player_id, cards(1), 8);
+        buffer.record_within(player_id, cards(2), 8);
+        assert_eq!(buffer.last_seq(player_id), 2);
+        assert!(!buffer.was_resynced(player_id, 2));
+        buffer.mark_resynced(player_id);
+        assert!(buffer.was_resynced(player_id, 1));
+        assert!(buffer.was_resynced(player_id, 2));
+        buffer.record_within(player_id, cards(3), 8);
+        assert!(!buffer.was_resynced(player_id, 3));
+        let stranger: PlayerId = uuid::Uuid::new_v4();
+        buffer.mark_resynced(stranger);
+        assert_eq!(buffer.last_seq(stranger), 0);
+        assert!(!buffer.was_resynced(stranger, 1));
+    }
+
+    #[test]
+    fn history_keeps_the_buffered_messages_oldest_first() {
+        let mut buffer: ReplayBuffer = ReplayBuffer::default();
+        let player_id: PlayerId = uuid::Uuid::new_v4();
+        for count in 1..=4 {
+            buffer.record_within(player_id, cards(count), 2);
+        }
+        let history: Vec<u64> = buffer.history(player_id).iter().map(seq_of).collect();
+        assert_eq!(history, vec![3, 4]);
+        assert!(buffer.history(uuid::Uuid::new_v4()).is_empty());
+        buffer.clear();
+        assert!(buffer.history(player_id).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::{PlayerId, TeamId};
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Team {
     pub id: TeamId,
     pub name: String,
@@ -1,8 +1,14 @@
-use tokio::{net::TcpListener, task::JoinHandle};
+use std::net::SocketAddr;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+    task::JoinHandle,
+};
 use tokio_rustls::TlsAcceptor;
 
 use crate::{
     auth::{identify_and_decode_token, GameSessionClaims, ReconnectClaims, SessionTokenType},
+    database::{BanScope, UserBan, UserRepository},
     network::{close_connection, receive_message, send_message},
     prelude::*,
 };
@@ -29,7 +35,7 @@ async fn handshake(connection: &mut Stream) -> Result<()> {
     }
 }
 
-pub async fn handle_client(connection: &mut Stream) -> Result<SessionTokenType> {
+pub async fn handle_client(connection: &mut Stream) -> Result<(SessionTokenType, Option<u64>)> {
     handshake(connection).await?;
     send_message(
         connection,
@@ -41,8 +47,19 @@ pub async fn handle_client(connection: &mut Stream) -> Result<SessionTokenType>
             if token.is_empty() {
                 return Err(Error::Other("Empty game session token".to_string()));
             }
-            identify_and_decode_token(&token)
+            Ok((identify_and_decode_token(&token)?, None))
         }
+        GameMessage::Reconnect { token, last_seq } => match identify_and_decode_token(&token)? {
+            SessionTokenType::Reconnection(claims) => {
+                Ok((SessionTokenType::Reconnection(claims), Some(last_seq)))
+            }
+            SessionTokenType::GameSession(_) => {
+                close_connection(connection).await?;
+                Err(Error::Other(
+                    "Reconnect requires a reconnection token".to_string(),
+                ))
+            }
+        },
         invalid => {
             close_connection(connection).await?;
             Err(Error::InvalidResponse(
@@ -53,7 +70,9 @@ pub async fn handle_client(connection: &mut Stream) -> Result<SessionTokenType>
     }
 }
 
-pub fn get_game_session_info(claims: GameSessionClaims) -> Result<(UserId, String, String)> {
+pub fn get_game_session_info(
+    claims: GameSessionClaims,
+) -> Result<(UserId, String, String, uuid::Uuid)> {
     let now: usize = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|_| Error::Other("System time error".to_string()))?
@@ -61,10 +80,10 @@ pub fn get_game_session_info(claims: GameSessionClaims) -> Result<(UserId, Strin
     if claims.exp < now {
         return Err(Error::GameTokenExpired);
     }
-    Ok((claims.sub, claims.username, claims.game_choice))
+    Ok((claims.sub, claims.username, claims.game_choice, claims.jti))
 }
 
-pub fn get_reconnection_info(claims: ReconnectClaims) -> Result<(PlayerId, GameId)> {
+pub fn get_reconnection_info(claims: ReconnectClaims) -> Result<(PlayerId, GameId, uuid::Uuid)> {
     let now: usize = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|_| Error::Other("System time error".to_string()))?
@@ -72,19 +91,77 @@ pub fn get_reconnection_info(claims: ReconnectClaims) -> Result<(PlayerId, GameI
     if claims.exp < now {
         return Err(Error::GameTokenExpired);
     }
-    Ok((claims.sub, claims.game_id))
+    Ok((claims.sub, claims.game_id, claims.jti))
+}
+
+/// Turns away tokens whose user was locked or deleted, or whose login was revoked, since
+/// game tokens outlive the access token they were issued for. Unlike bans this fails closed.
+async fn reject_if_unauthorized(
+    user_repo: &UserRepository,
+    user_id: UserId,
+    jti: uuid::Uuid,
+    connection: &mut Stream,
+) -> bool {
+    match crate::auth::authorize_session(user_repo, user_id, jti).await {
+        Ok(Some(_)) => return false,
+        Ok(None) => println!("Rejected revoked game token of user {user_id}"),
+        Err(err) => eprintln!("Failed to authorize user {user_id}: {err}"),
+    }
+    let _ = close_connection(connection).await;
+    true
+}
+
+/// Turns away users with an active ban covering `scope`, telling them why. Failing to read
+/// the bans lets the user through rather than locking everyone out while the database is
+/// unavailable.
+async fn reject_if_banned(
+    user_repo: &UserRepository,
+    user_id: UserId,
+    scope: BanScope,
+    connection: &mut Stream,
+) -> bool {
+    let ban: UserBan = match user_repo.get_active_ban(user_id, scope).await {
+        Ok(Some(ban)) => ban,
+        Ok(None) => return false,
+        Err(err) => {
+            eprintln!("Failed to check the bans of user {user_id}: {err}");
+            return false;
+        }
+    };
+    println!("Rejected banned user {user_id} from the game server");
+    let _ = send_message(
+        connection,
+        &GameMessage::Banned {
+            message: ban.notice(),
+        },
+    )
+    .await;
+    let _ = close_connection(connection).await;
+    true
 }
 
-pub async fn init_game_server() -> Result<JoinHandle<()>> {
+pub async fn init_game_server(
+    user_repo: UserRepository,
+    mut shutdown: oneshot::Receiver<()>,
+) -> Result<JoinHandle<()>> {
     super::tls::init_crypto_provider();
     let tls_acceptor: TlsAcceptor = super::tls::get_tls_acceptor()?;
     let listener: TcpListener = get_listener().await?;
+    crate::core::get_game_registry().restore_saved_games().await;
     let game_server: JoinHandle<()> = tokio::spawn(async move {
         println!("Game server started successfully");
         loop {
-            match listener.accept().await {
+            let accepted: std::io::Result<(TcpStream, SocketAddr)> = tokio::select! {
+                accepted = listener.accept() => accepted,
+                _ = &mut shutdown => {
+                    println!("Game server stopped accepting connections");
+                    break;
+                }
+            };
+            match accepted {
                 Ok((stream, addr)) => {
                     let acceptor: TlsAcceptor = tls_acceptor.clone();
+                    let user_repo: UserRepository = user_repo.clone();
                     tokio::spawn(async move {
                         let mut tls_stream: Stream = match acceptor.accept(stream).await {
                             Ok(tls_stream) => Stream::Server(tls_stream),
@@ -94,15 +171,33 @@ pub async fn init_game_server() -> Result<JoinHandle<()>> {
                             }
                         };
                         match handle_client(&mut tls_stream).await {
-                            Ok(SessionTokenType::GameSession(claims)) => {
+                            Ok((SessionTokenType::GameSession(claims), _)) => {
                                 match get_game_session_info(claims) {
-                                    Ok((user_id, username, game_choice)) => {
+                                    Ok((user_id, username, game_choice, jti)) => {
+                                        if reject_if_unauthorized(
+                                            &user_repo,
+                                            user_id,
+                                            jti,
+                                            &mut tls_stream,
+                                        )
+                                        .await
+                                            || reject_if_banned(
+                                                &user_repo,
+                                                user_id,
+                                                BanScope::Matchmaking,
+                                                &mut tls_stream,
+                                            )
+                                            .await
+                                        {
+                                            return;
+                                        }
                                         println!("Player {username} wants to play {game_choice}");
                                         if let Err(err) = crate::core::get_game_registry()
                                             .add_player_to_queue(
                                                 user_id,
                                                 username.clone(),
                                                 game_choice.clone(),
+                                                jti,
                                                 tls_stream,
                                             )
                                             .await
@@ -115,14 +210,35 @@ pub async fn init_game_server() -> Result<JoinHandle<()>> {
                                     }
                                 }
                             }
-                            Ok(SessionTokenType::Reconnection(claims)) => {
+                            Ok((SessionTokenType::Reconnection(claims), last_seq)) => {
                                 match get_reconnection_info(claims) {
-                                    Ok((player_id, game_id)) => {
+                                    // Only login bans stop a reconnection: a matchmaking ban
+                                    // lets the player finish the game they are already in.
+                                    Ok((player_id, game_id, jti)) => {
+                                        if reject_if_unauthorized(
+                                            &user_repo,
+                                            player_id,
+                                            jti,
+                                            &mut tls_stream,
+                                        )
+                                        .await
+                                            || reject_if_banned(
+                                                &user_repo,
+                                                player_id,
+                                                BanScope::Login,
+                                                &mut tls_stream,
+                                            )
+                                            .await
+                                        {
+                                            return;
+                                        }
                                         println!(
                                             "Player {player_id} wants to reconnect to {game_id}"
                                         );
                                         if let Err(err) = crate::core::get_game_registry()
-                                            .reconnect_player(player_id, game_id, tls_stream)
+                                            .reconnect_player(
+                                                player_id, game_id, tls_stream, last_seq,
+                                            )
                                             .await
                                         {
                                             eprintln!("Failed to reconnect player {player_id} to game {game_id}: {err}");